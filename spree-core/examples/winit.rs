@@ -161,16 +161,15 @@ impl ortelius::State for State {
                     .iter()
                     .enumerate()
                     .map(|(i, buffer)| layer::Line {
-                        data: buffer,
                         thickness: 3.,
                         colour: COLOURS[i],
+                        ..layer::Line::new(buffer)
                     })
                     .collect(),
             ),
             Layer::Scatters(vec![layer::Scatter {
-                data: self.line_buffers.first().unwrap(),
                 radius: 2.5,
-                colour: [0., 0., 0., 1.],
+                ..layer::Scatter::new(self.line_buffers.first().unwrap())
             }]),
         ]
     }
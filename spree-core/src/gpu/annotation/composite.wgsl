@@ -0,0 +1,39 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    _padding: vec4<f32>,
+}
+@group(1) @binding(0) var<uniform> params: Params;
+// Drawn by vello at the size of the framebuffer, with premultiplied alpha.
+@group(1) @binding(1) var overlay: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+};
+
+// Covers the whole viewport.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let ndc = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u)) * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(overlay));
+    let texel = textureLoad(overlay, min(vec2<i32>(in.position.xy), size - 1), 0);
+    if (texel.a <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(texel.rgb / texel.a, texel.a);
+}
@@ -0,0 +1,238 @@
+use std::f64::consts::PI;
+
+use bytemuck::{Pod, Zeroable};
+use vello::{
+    Scene,
+    kurbo::{Affine, BezPath, Line, Point, Rect, Stroke, Vec2},
+    peniko::{Color, Fill},
+    wgpu,
+};
+
+use super::{LayerRenderer, SceneParams, text::TextLayout, to_buffer};
+use crate::layer::{Annotation, HorizontalAlign, VerticalAlign};
+
+/// A texture that vello has drawn annotations into, composited over the
+/// framebuffer.
+pub(super) struct Overlay<'a> {
+    pub(super) view: &'a wgpu::TextureView,
+}
+
+impl super::Layer for Overlay<'_> {
+    const HAS_DATA: bool = false;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        panic!("annotation layer does not have a buffer")
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerOverlayParams {
+    _padding: [f32; 4],
+}
+
+pub(super) struct Renderer {}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Overlay<'a>;
+    type PerLayerParams = PerOverlayParams;
+
+    const NAME: &'static str = "annotation";
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        wgpu::include_wgsl!("composite.wgsl")
+    }
+
+    fn counts(&self, _: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..4, 0..1)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITHOUT_POINTS[0],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, _: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerOverlayParams { _padding: [0.; 4] }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(layer.view),
+                },
+            ],
+        })
+    }
+}
+
+/// Creates a texture for vello to draw into that the overlay can read.
+pub(super) fn create_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("annotation texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Builds a vello scene drawing `annotations` at the current view.
+///
+/// `scale` is the number of physical pixels per logical pixel.
+pub(super) fn build_scene(
+    annotations: &[Annotation<'_>],
+    scene_params: &SceneParams,
+    scale: f64,
+) -> Scene {
+    let mut scene = Scene::new();
+    for annotation in annotations {
+        draw_annotation(&mut scene, annotation, scene_params, Affine::scale(scale));
+    }
+    scene
+}
+
+fn draw_annotation(
+    scene: &mut Scene,
+    annotation: &Annotation<'_>,
+    scene_params: &SceneParams,
+    transform: Affine,
+) {
+    let [x, y] = annotation.position;
+    let target = scene_params.pixel_transform() * scene_params.map_to_plane(Point::new(x, y));
+    let [dx, dy] = annotation.offset.map(f64::from);
+    let anchor = target + Vec2::new(dx, -dy);
+
+    if let Some(arrow) = annotation.arrow {
+        draw_arrow(
+            scene,
+            transform,
+            anchor,
+            target,
+            arrow.thickness,
+            arrow.colour,
+        );
+    }
+
+    let text = TextLayout::new(annotation.text, annotation.font, annotation.size);
+    let left = match annotation.align.0 {
+        HorizontalAlign::Left => 0.,
+        HorizontalAlign::Centre => -text.width / 2.,
+        HorizontalAlign::Right => -text.width,
+    };
+    let top = match annotation.align.1 {
+        VerticalAlign::Top => 0.,
+        VerticalAlign::Middle => -text.height / 2.,
+        VerticalAlign::Bottom => -text.height,
+    };
+    let transform = transform
+        * Affine::translate(anchor.to_vec2())
+        * Affine::rotate(-annotation.rotation as f64 * PI / 180.)
+        * Affine::translate((left, top));
+
+    if let Some(background) = annotation.background {
+        let padding = annotation.size as f64 / 4.;
+        let rect = Rect::new(0., 0., text.width, text.height).inflate(padding, padding);
+        scene.fill(
+            Fill::NonZero,
+            transform,
+            Color::new(background),
+            None,
+            &rect,
+        );
+    }
+
+    text.draw(
+        scene,
+        annotation.font,
+        annotation.size,
+        transform,
+        annotation.align.0,
+        annotation.colour,
+    );
+}
+
+/// Draws a line from `start` to `end` with a triangular head at `end`.
+fn draw_arrow(
+    scene: &mut Scene,
+    transform: Affine,
+    start: Point,
+    end: Point,
+    thickness: f32,
+    colour: [f32; 4],
+) {
+    let direction = end - start;
+    let length = direction.hypot();
+    if length < 1. {
+        return;
+    }
+    let direction = direction / length;
+    let normal = Vec2::new(-direction.y, direction.x);
+
+    let thickness = thickness as f64;
+    let head_length = (thickness * 4. + 4.).min(length);
+    let head_base = end - direction * head_length;
+    let head_width = head_length / 2.;
+
+    let brush = Color::new(colour);
+    scene.stroke(
+        &Stroke::new(thickness),
+        transform,
+        brush,
+        None,
+        &Line::new(start, head_base),
+    );
+
+    let mut head = BezPath::new();
+    head.move_to(end);
+    head.line_to(head_base + normal * head_width);
+    head.line_to(head_base - normal * head_width);
+    head.close_path();
+    scene.fill(Fill::NonZero, transform, brush, None, &head);
+}
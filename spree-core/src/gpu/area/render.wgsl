@@ -0,0 +1,62 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    colour: vec4<f32>,
+    baseline: f32,
+    // Whether to fill to `baseline_points` instead of `baseline`.
+    has_baseline_points: u32,
+    _pad_0: f32,
+    _pad_1: f32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+@group(1) @binding(2) var<storage, read> baseline_points: array<vec2<f32>>;
+
+fn baseline_point(i: u32) -> vec2<f32> {
+    if (params.has_baseline_points == 1u) {
+        return baseline_points[i];
+    }
+    return vec2<f32>(points[i].x, params.baseline);
+}
+
+// Each instance fills the area between points[i], points[i + 1] and the
+// baseline below (or above) them with two triangles.
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> @builtin(position) vec4<f32> {
+    let t0 = points[instance_index];
+    let t1 = points[instance_index + 1u];
+    let b0 = baseline_point(instance_index);
+    let b1 = baseline_point(instance_index + 1u);
+
+    if (is_gap(t0) || is_gap(t1) || is_gap(b0) || is_gap(b1)) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    var triangles = array<vec2<f32>, 6>(t0, b0, t1, t1, b0, b1);
+
+    // Where the data crosses the baseline, fill the two sides of the crossing
+    // separately so the triangles don't overlap.
+    let h0 = t0.y - b0.y;
+    let h1 = t1.y - b1.y;
+    if (h0 * h1 < 0.0) {
+        let crossing = mix(t0, t1, h0 / (h0 - h1));
+        triangles = array<vec2<f32>, 6>(t0, b0, crossing, crossing, t1, b1);
+    }
+
+    return scene.projection_matrix * vec4<f32>(to_plane(triangles[vertex_index]), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return params.colour;
+}
@@ -0,0 +1,70 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+const ORIENTATION_VERTICAL: u32 = 0u;
+const ORIENTATION_HORIZONTAL: u32 = 1u;
+
+struct Params {
+    colour: vec4<f32>,
+    width: f32,
+    offset: f32,
+    baseline: f32,
+    // Whether bars start at `baseline_points` instead of `baseline`.
+    has_baseline_points: u32,
+    orientation: u32,
+    _pad_0: u32,
+    _pad_1: u32,
+    _pad_2: u32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+@group(1) @binding(2) var<storage, read> baseline_points: array<vec2<f32>>;
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> @builtin(position) vec4<f32> {
+    var point = points[instance_index];
+    var base = vec2<f32>(params.baseline);
+    if (params.has_baseline_points == 1u) {
+        base = baseline_points[instance_index];
+    }
+
+    if (is_gap(point) || is_gap(base)) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    // Work as if the bars are vertical, and swap the axes back at the end.
+    if (params.orientation == ORIENTATION_HORIZONTAL) {
+        point = point.yx;
+        base = base.yx;
+    }
+
+    let centre = point.x + params.offset;
+    let half_width = params.width / 2.0;
+    var corners = array<vec2<f32>, 4>(
+        vec2<f32>(centre - half_width, base.y),
+        vec2<f32>(centre + half_width, base.y),
+        vec2<f32>(centre - half_width, point.y),
+        vec2<f32>(centre + half_width, point.y),
+    );
+
+    var corner = corners[vertex_index];
+    if (params.orientation == ORIENTATION_HORIZONTAL) {
+        corner = corner.yx;
+    }
+
+    return scene.projection_matrix * vec4<f32>(to_plane(corner), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return params.colour;
+}
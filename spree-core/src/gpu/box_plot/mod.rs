@@ -0,0 +1,64 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use crate::layer::{BoxPlot, Orientation};
+
+/// The number of vertices drawing the box and whiskers.
+const BOX_VERTICES: u32 = 60;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerBoxPlotParams {
+    colour: [f32; 4],
+    line_colour: [f32; 4],
+    quartiles: [f32; 3],
+    position: f32,
+    whiskers: [f32; 2],
+    width: f32,
+    thickness: f32,
+    outlier_radius: f32,
+    orientation: u32,
+    _padding: [u32; 2],
+}
+
+pub(super) struct Renderer {}
+
+impl super::LayerRenderer for Renderer {
+    type Layer<'a> = BoxPlot<'a>;
+    type PerLayerParams = PerBoxPlotParams;
+
+    const NAME: &'static str = "box plot";
+
+    const TOPOLOGY: wgpu::PrimitiveTopology = wgpu::PrimitiveTopology::TriangleList;
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["polar.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        // The first instance draws the box, and the rest draw an outlier each.
+        (0..BOX_VERTICES, 0..data.data.outlier_count() as u32 + 1)
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerBoxPlotParams {
+            colour: data.colour,
+            line_colour: data.lines.colour,
+            quartiles: data.data.quartiles(),
+            position: data.position,
+            whiskers: data.data.whiskers(),
+            width: data.width,
+            thickness: data.lines.thickness,
+            outlier_radius: data.outlier_radius,
+            orientation: match data.orientation {
+                Orientation::Vertical => 0,
+                Orientation::Horizontal => 1,
+            },
+            _padding: [0, 0],
+        }
+    }
+}
@@ -0,0 +1,194 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+const ORIENTATION_HORIZONTAL: u32 = 1u;
+
+struct Params {
+    colour: vec4<f32>,
+    line_colour: vec4<f32>,
+    // The lower quartile, median and upper quartile.
+    quartiles: vec3<f32>,
+    position: f32,
+    whiskers: vec2<f32>,
+    width: f32,
+    thickness: f32,
+    outlier_radius: f32,
+    orientation: u32,
+    _pad_0: u32,
+    _pad_1: u32,
+}
+@group(1) @binding(0) var<storage, read> outliers: array<f32>;
+@group(1) @binding(1) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) colour: vec4<f32>,
+    // The position relative to an outlier's centre in pixels, or zero.
+    @location(1) offset: vec2<f32>,
+};
+
+// Converts a position and a value to pixels from the centre of the viewport.
+fn to_screen(position: f32, value: f32) -> vec2<f32> {
+    var point = vec2<f32>(position, value);
+    if (params.orientation == ORIENTATION_HORIZONTAL) {
+        point = point.yx;
+    }
+    let ndc = (scene.projection_matrix * vec4<f32>(to_plane(point), 0.0, 1.0)).xy;
+    return ndc * 0.5 * scene.viewport_size;
+}
+
+struct Piece {
+    low: vec2<f32>,
+    high: vec2<f32>,
+    // Whether the piece is the box rather than a line.
+    is_fill: bool,
+}
+
+// The k'th piece of the box and whiskers, as a rectangle in pixels. Lines are
+// split so they don't overlap, which would show with translucent colours.
+fn piece(k: u32) -> Piece {
+    let half = params.width / 2.0;
+    let t = params.thickness / 2.0;
+    let q1 = to_screen(params.position - half, params.quartiles.x);
+    let q3 = to_screen(params.position + half, params.quartiles.z);
+    let low = min(q1, q3);
+    let high = max(q1, q3);
+    let median = to_screen(params.position, params.quartiles.y);
+    let whisker_low = to_screen(params.position - half / 2.0, params.whiskers.x);
+    let whisker_high = to_screen(params.position + half / 2.0, params.whiskers.y);
+    let centre = to_screen(params.position, 0.0);
+
+    // Work in a frame where the box is vertical, and swap back at the end.
+    let horizontal = params.orientation == ORIENTATION_HORIZONTAL;
+    var l = low;
+    var h = high;
+    var m = median;
+    var wl = min(whisker_low, whisker_high);
+    var wh = max(whisker_low, whisker_high);
+    var c = centre;
+    if (horizontal) {
+        l = l.yx;
+        h = h.yx;
+        m = m.yx;
+        wl = wl.yx;
+        wh = wh.yx;
+        c = c.yx;
+    }
+
+    var p = Piece(vec2<f32>(0.0), vec2<f32>(0.0), false);
+    switch k {
+        case 0u: {
+            p = Piece(l, h, true);
+        }
+        // The sides of the box, then the top and bottom between them.
+        case 1u: {
+            p = Piece(vec2<f32>(l.x - t, l.y - t), vec2<f32>(l.x + t, h.y + t), false);
+        }
+        case 2u: {
+            p = Piece(vec2<f32>(h.x - t, l.y - t), vec2<f32>(h.x + t, h.y + t), false);
+        }
+        case 3u: {
+            p = Piece(vec2<f32>(l.x + t, l.y - t), vec2<f32>(h.x - t, l.y + t), false);
+        }
+        case 4u: {
+            p = Piece(vec2<f32>(l.x + t, h.y - t), vec2<f32>(h.x - t, h.y + t), false);
+        }
+        case 5u: {
+            p = Piece(vec2<f32>(l.x + t, m.y - t), vec2<f32>(h.x - t, m.y + t), false);
+        }
+        // The whiskers, then their caps.
+        case 6u: {
+            if (wl.y < l.y - t) {
+                p = Piece(vec2<f32>(c.x - t, wl.y + t), vec2<f32>(c.x + t, l.y - t), false);
+            }
+        }
+        case 7u: {
+            if (wh.y > h.y + t) {
+                p = Piece(vec2<f32>(c.x - t, h.y + t), vec2<f32>(c.x + t, wh.y - t), false);
+            }
+        }
+        case 8u: {
+            if (wl.y < l.y - t) {
+                p = Piece(vec2<f32>(wl.x, wl.y - t), vec2<f32>(wh.x, wl.y + t), false);
+            }
+        }
+        case 9u: {
+            if (wh.y > h.y + t) {
+                p = Piece(vec2<f32>(wl.x, wh.y - t), vec2<f32>(wh.x, wh.y + t), false);
+            }
+        }
+        default: {}
+    }
+
+    if (horizontal) {
+        p.low = p.low.yx;
+        p.high = p.high.yx;
+    }
+    return p;
+}
+
+// Each vertex is a corner of a rectangle made of two triangles.
+fn corner(vertex_index: u32) -> vec2<f32> {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    return corners[vertex_index % 6u];
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    out.offset = vec2<f32>(0.0);
+
+    var screen: vec2<f32>;
+    if (instance_index == 0u) {
+        let p = piece(vertex_index / 6u);
+        if (any(p.high <= p.low)) {
+            return out;
+        }
+        screen = mix(p.low, p.high, corner(vertex_index));
+        out.colour = select(params.line_colour, params.colour, p.is_fill);
+    } else {
+        // Outliers only use the first rectangle.
+        if (vertex_index >= 6u) {
+            return out;
+        }
+        let centre = to_screen(params.position, outliers[instance_index - 1u]);
+        let extent = params.outlier_radius + 1.0;
+        out.offset = (corner(vertex_index) * 2.0 - 1.0) * extent;
+        screen = centre + out.offset;
+        out.colour = params.line_colour;
+    }
+
+    out.position = vec4<f32>(screen / (0.5 * scene.viewport_size), 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    if (all(in.offset == vec2<f32>(0.0))) {
+        return in.colour;
+    }
+
+    // Outliers are antialiased circles.
+    let alpha = clamp(params.outlier_radius + 0.5 - length(in.offset), 0.0, 1.0);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(in.colour.rgb, in.colour.a * alpha);
+}
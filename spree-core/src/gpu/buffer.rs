@@ -145,6 +145,47 @@ where
         encoder.finish()
     }
 
+    /// Overwrites `length` values starting at `start`, which must already be
+    /// in the buffer.
+    #[inline]
+    #[must_use]
+    pub fn write<F>(
+        &mut self,
+        device: &wgpu::Device,
+        start: usize,
+        length: usize,
+        fill: F,
+    ) -> CommandBuffer
+    where
+        F: FnOnce(&mut [T]),
+    {
+        assert!(
+            start + length <= self.length,
+            "Written values must already be in the buffer"
+        );
+
+        let size = (length * std::mem::size_of::<T>()) as u64;
+        let temp = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: pad_size(size),
+            usage: self.usage,
+            mapped_at_creation: true,
+        });
+        {
+            let mut buffer_view = temp.slice(..).get_mapped_range_mut();
+            let buffer_view_2 = &mut bytemuck::cast_slice_mut(buffer_view.as_mut())[..length];
+            fill(buffer_view_2);
+        }
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Write Command Encoder"),
+        });
+        let offset = (start * std::mem::size_of::<T>()) as u64;
+        encoder.copy_buffer_to_buffer(&temp, 0, &self.inner, offset, size);
+
+        encoder.finish()
+    }
+
     #[inline]
     #[must_use]
     pub(crate) fn as_entire_binding(&self) -> BindingResource<'_> {
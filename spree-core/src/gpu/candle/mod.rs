@@ -0,0 +1,53 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use crate::layer::{CandleStyle, Candles};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerCandlesParams {
+    up_colour: [f32; 4],
+    down_colour: [f32; 4],
+    width: f32,
+    thickness: f32,
+    style: u32,
+    _padding: u32,
+}
+
+pub(super) struct Renderer {}
+
+impl super::LayerRenderer for Renderer {
+    type Layer<'a> = Candles<'a>;
+    type PerLayerParams = PerCandlesParams;
+
+    const NAME: &'static str = "candle";
+
+    const TOPOLOGY: wgpu::PrimitiveTopology = wgpu::PrimitiveTopology::TriangleList;
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl", "polar.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        // Three quads per candle: the wick, and then the body or the two ticks.
+        (0..18, 0..data.data.len() as u32)
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerCandlesParams {
+            up_colour: data.up_colour,
+            down_colour: data.down_colour,
+            width: data.width,
+            thickness: data.thickness,
+            style: match data.style {
+                CandleStyle::Candlestick => 0,
+                CandleStyle::Ohlc => 1,
+            },
+            _padding: 0,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+const STYLE_CANDLESTICK: u32 = 0u;
+
+struct Candle {
+    time: f32,
+    open: f32,
+    high: f32,
+    low: f32,
+    close: f32,
+}
+
+struct Params {
+    up_colour: vec4<f32>,
+    down_colour: vec4<f32>,
+    width: f32,
+    thickness: f32,
+    style: u32,
+    _pad_0: u32,
+}
+@group(1) @binding(0) var<storage, read> candles: array<Candle>;
+@group(1) @binding(1) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) colour: vec4<f32>,
+};
+
+// A candle with any NaN value marks a gap in the data.
+fn is_candle_gap(candle: Candle) -> bool {
+    return is_nan(candle.time) || is_nan(candle.open) || is_nan(candle.high)
+        || is_nan(candle.low) || is_nan(candle.close);
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> VertexOutput {
+    var out: VertexOutput;
+    let candle = candles[instance_index];
+    if (is_candle_gap(candle)) {
+        out.position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        return out;
+    }
+
+    let half_width = params.width / 2.0;
+    let half_thickness = params.thickness / 2.0;
+    let quad = vertex_index / 6u;
+
+    // Each quad covers a rectangle in data units, grown by a number of pixels
+    // so that lines and flat bodies stay visible.
+    var low = vec2<f32>(candle.time, candle.low);
+    var high = vec2<f32>(candle.time, candle.high);
+    var grow = vec2<f32>(half_thickness, 0.0);
+    if (quad == 1u) {
+        if (params.style == STYLE_CANDLESTICK) {
+            low = vec2<f32>(candle.time - half_width, min(candle.open, candle.close));
+            high = vec2<f32>(candle.time + half_width, max(candle.open, candle.close));
+            grow = vec2<f32>(0.0, half_thickness);
+        } else {
+            low = vec2<f32>(candle.time - half_width, candle.open);
+            high = vec2<f32>(candle.time, candle.open);
+            grow = vec2<f32>(0.0, half_thickness);
+        }
+    } else if (quad == 2u) {
+        if (params.style == STYLE_CANDLESTICK) {
+            out.position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+            return out;
+        }
+        low = vec2<f32>(candle.time, candle.close);
+        high = vec2<f32>(candle.time + half_width, candle.close);
+        grow = vec2<f32>(0.0, half_thickness);
+    }
+
+    // Two triangles, as corners of the unit square.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index % 6u];
+
+    let world = mix(low, high, corner);
+    let clip = scene.projection_matrix * vec4<f32>(to_plane(world), 0.0, 1.0);
+    let offset = (corner * 2.0 - 1.0) * grow / scene.viewport_size * 2.0;
+    out.position = vec4<f32>(clip.xy + offset * clip.w, clip.zw);
+    out.colour = select(params.down_colour, params.up_colour, candle.close >= candle.open);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.colour;
+}
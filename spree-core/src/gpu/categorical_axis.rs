@@ -0,0 +1,154 @@
+use std::{borrow::Cow, f64::consts::FRAC_PI_4};
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Line, Point, Rect, Stroke, Vec2},
+    peniko::Color,
+};
+
+use super::{SceneParams, text::TextLayout};
+use crate::{
+    layer::{Axis, CategoricalAxis, HorizontalAlign},
+    layout::Padding,
+};
+
+const ELLIPSIS: char = '…';
+
+/// Builds a vello scene drawing the ticks and labels of `axes` at the current
+/// view.
+///
+/// `scale` is the number of physical pixels per logical pixel.
+pub(super) fn build_scene(
+    axes: &[CategoricalAxis<'_>],
+    scene_params: &SceneParams,
+    padding: &Padding,
+    scale: f64,
+) -> Scene {
+    let mut scene = Scene::new();
+    // Categories are placed along Cartesian axes only.
+    if scene_params.is_polar() {
+        return scene;
+    }
+
+    let [viewport_width, viewport_height] = scene_params.viewport_size.map(f64::from);
+    let inner = Rect::new(
+        padding.left,
+        padding.top,
+        viewport_width - padding.right,
+        viewport_height - padding.bottom,
+    );
+    for axis in axes {
+        draw_axis(&mut scene, axis, scene_params, inner, Affine::scale(scale));
+    }
+    scene
+}
+
+fn draw_axis(
+    scene: &mut Scene,
+    axis: &CategoricalAxis<'_>,
+    scene_params: &SceneParams,
+    inner: Rect,
+    transform: Affine,
+) {
+    let to_pixels = scene_params.pixel_transform();
+    let [x_scale, _, _, y_scale, ..] = to_pixels.as_coeffs();
+    let size = axis.size as f64;
+    let gap = size / 2.;
+    let labels: Vec<_> = axis
+        .categories
+        .iter()
+        .map(|category| {
+            let text = truncate(category, axis, axis.max_label_width as f64);
+            TextLayout::new(&text, axis.font, axis.size)
+        })
+        .collect();
+    let width = labels.iter().map(|label| label.width).fold(0., f64::max);
+    let height = labels.iter().map(|label| label.height).fold(0., f64::max);
+
+    // The distance between categories and the space each label takes along
+    // the axis, both in pixels.
+    let (spacing, footprint, angle) = match axis.axis {
+        Axis::X => {
+            let spacing = x_scale.abs();
+            if !axis.rotate_labels || width + gap <= spacing {
+                (spacing, width, 0.)
+            } else if height * 2f64.sqrt() + gap <= spacing {
+                (spacing, height * 2f64.sqrt(), -FRAC_PI_4)
+            } else {
+                (spacing, height, -2. * FRAC_PI_4)
+            }
+        }
+        Axis::Y => (y_scale.abs(), height, 0.),
+    };
+    let rotated = angle != 0.;
+    let step = ((footprint + gap) / spacing).ceil().max(1.) as usize;
+
+    let tick_stroke = Stroke::new(1.);
+    let tick_colour = Color::new(axis.colour);
+    for (i, label) in labels.into_iter().enumerate() {
+        let point = match axis.axis {
+            Axis::X => Point::new((to_pixels * Point::new(i as f64, 0.)).x, inner.y1),
+            Axis::Y => Point::new(inner.x0, (to_pixels * Point::new(0., i as f64)).y),
+        };
+        if point.x < inner.x0 - 0.5
+            || point.x > inner.x1 + 0.5
+            || point.y < inner.y0 - 0.5
+            || point.y > inner.y1 + 0.5
+        {
+            continue;
+        }
+
+        let direction = match axis.axis {
+            Axis::X => Vec2::new(0., 1.),
+            Axis::Y => Vec2::new(-1., 0.),
+        };
+        let tick_end = point + direction * axis.tick_length as f64;
+        scene.stroke(
+            &tick_stroke,
+            transform,
+            tick_colour,
+            None,
+            &Line::new(point, tick_end),
+        );
+        if i % step != 0 {
+            continue;
+        }
+
+        // Unrotated labels hang centred below the x axis, and the rest end
+        // at the tick.
+        let anchor = tick_end + direction * gap / 2.;
+        let origin = if axis.axis == Axis::X && !rotated {
+            Vec2::new(-label.width / 2., 0.)
+        } else {
+            Vec2::new(-label.width, -label.height / 2.)
+        };
+        let label_transform = transform
+            * Affine::translate(anchor.to_vec2())
+            * Affine::rotate(angle)
+            * Affine::translate(origin);
+        label.draw(
+            scene,
+            axis.font,
+            axis.size,
+            label_transform,
+            HorizontalAlign::Left,
+            axis.colour,
+        );
+    }
+}
+
+/// Shortens `text` with an ellipsis until it is at most `max_width` wide.
+fn truncate<'a>(text: &'a str, axis: &CategoricalAxis<'_>, max_width: f64) -> Cow<'a, str> {
+    if TextLayout::new(text, axis.font, axis.size).width <= max_width {
+        return Cow::Borrowed(text);
+    }
+
+    let mut truncated: String = text.into();
+    while truncated.pop().is_some() {
+        let candidate = format!("{}{ELLIPSIS}", truncated.trim_end());
+        if TextLayout::new(&candidate, axis.font, axis.size).width <= max_width {
+            return Cow::Owned(candidate);
+        }
+    }
+    Cow::Owned(ELLIPSIS.into())
+}
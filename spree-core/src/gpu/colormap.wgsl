@@ -0,0 +1,10 @@
+// Prepended to shaders by `include_wgsl_with!`. Expects the shader to bind
+// the colormap's stops as `colormap`.
+
+// Samples the colormap at `t`, clamped to [0, 1], interpolating between stops.
+fn sample_colormap(t: f32) -> vec4<f32> {
+    let last = arrayLength(&colormap) - 1u;
+    let position = clamp(t, 0.0, 1.0) * f32(last);
+    let i = min(u32(position), last);
+    return mix(colormap[i], colormap[min(i + 1u, last)], position - f32(i));
+}
@@ -0,0 +1,76 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    // The minimum x and y, then the maximum x and y, of the image in data
+    // coordinates.
+    bounds: vec4<f32>,
+    colour: vec4<f32>,
+    thickness: f32,
+    level_count: u32,
+    _pad_0: f32,
+    _pad_1: f32,
+    levels: array<vec4<f32>, 4>,
+}
+@group(1) @binding(0) var<uniform> params: Params;
+@group(1) @binding(1) var image: texture_2d<f32>;
+@group(1) @binding(2) var<storage, read> colormap: array<vec4<f32>>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // The position in texels from the centre of the first texel.
+    @location(0) texel: vec2<f32>,
+}
+
+fn level(i: u32) -> f32 {
+    return params.levels[i / 4u][i % 4u];
+}
+
+// Covers the texels, whose values are placed at the centres of the image's
+// cells.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let size = vec2<f32>(textureDimensions(image));
+    let uv = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u));
+    let texel = uv * (size - 1.0);
+    let point = mix(params.bounds.xy, params.bounds.zw, (texel + 0.5) / size);
+
+    var out: VertexOutput;
+    out.position = scene.projection_matrix * vec4<f32>(point, 0.0, 1.0);
+    out.texel = texel;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = textureDimensions(image);
+    let origin = min(vec2<u32>(in.texel), size - 2u);
+    let t = in.texel - vec2<f32>(origin);
+
+    let v00 = textureLoad(image, origin, 0).r;
+    let v10 = textureLoad(image, origin + vec2<u32>(1u, 0u), 0).r;
+    let v01 = textureLoad(image, origin + vec2<u32>(0u, 1u), 0).r;
+    let v11 = textureLoad(image, origin + vec2<u32>(1u, 1u), 0).r;
+    let value = mix(mix(v00, v10, t.x), mix(v01, v11, t.x), t.y);
+    if (is_nan(value)) {
+        discard;
+    }
+
+    // Values outside of the levels aren't in any band.
+    var band = 0u;
+    while (band < params.level_count && value >= level(band)) {
+        band++;
+    }
+    if (band == 0u || band == params.level_count) {
+        discard;
+    }
+
+    let bands = f32(params.level_count - 1u);
+    return sample_colormap((f32(band) - 0.5) / bands);
+}
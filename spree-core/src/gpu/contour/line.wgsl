@@ -0,0 +1,175 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    // The minimum x and y, then the maximum x and y, of the image in data
+    // coordinates.
+    bounds: vec4<f32>,
+    colour: vec4<f32>,
+    thickness: f32,
+    level_count: u32,
+    _pad_0: f32,
+    _pad_1: f32,
+    levels: array<vec4<f32>, 4>,
+}
+@group(1) @binding(0) var<uniform> params: Params;
+@group(1) @binding(1) var image: texture_2d<f32>;
+
+// Marks a missing edge in the segment table.
+const NONE: u32 = 4u;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // The position and the segment's end points relative to the centre of the
+    // viewport in logical pixels.
+    @location(0) pixel: vec2<f32>,
+    @location(1) @interpolate(flat) start: vec2<f32>,
+    @location(2) @interpolate(flat) end: vec2<f32>,
+}
+
+fn level(i: u32) -> f32 {
+    return params.levels[i / 4u][i % 4u];
+}
+
+fn to_screen(point: vec2<f32>) -> vec2<f32> {
+    let ndc = (scene.projection_matrix * vec4<f32>(point, 0.0, 1.0)).xy;
+    return ndc * 0.5 * scene.viewport_size;
+}
+
+// The value of each texel is placed at the centre of its cell.
+fn texel_position(texel: vec2<f32>) -> vec2<f32> {
+    let size = vec2<f32>(textureDimensions(image));
+    return mix(params.bounds.xy, params.bounds.zw, (texel + 0.5) / size);
+}
+
+// The pair of edges crossed by the `k`th segment through a cell, numbering the
+// edges anticlockwise from the bottom. Corners are numbered in the same order
+// from the bottom left, and `above` has a bit set for each corner above the
+// level.
+fn segment_edges(above: u32, centre_above: bool, k: u32) -> vec2<u32> {
+    var edges = array<vec2<u32>, 2>(vec2<u32>(NONE), vec2<u32>(NONE));
+    switch above {
+        case 1u, 14u: {
+            edges[0] = vec2<u32>(3u, 0u);
+        }
+        case 2u, 13u: {
+            edges[0] = vec2<u32>(0u, 1u);
+        }
+        case 3u, 12u: {
+            edges[0] = vec2<u32>(3u, 1u);
+        }
+        case 4u, 11u: {
+            edges[0] = vec2<u32>(1u, 2u);
+        }
+        case 6u, 9u: {
+            edges[0] = vec2<u32>(0u, 2u);
+        }
+        case 7u, 8u: {
+            edges[0] = vec2<u32>(2u, 3u);
+        }
+        // At saddles, the centre decides which pair of opposite corners are
+        // cut off.
+        case 5u, 10u: {
+            if (centre_above == (above == 5u)) {
+                edges = array<vec2<u32>, 2>(vec2<u32>(0u, 1u), vec2<u32>(2u, 3u));
+            } else {
+                edges = array<vec2<u32>, 2>(vec2<u32>(3u, 0u), vec2<u32>(1u, 2u));
+            }
+        }
+        default: {}
+    }
+    return edges[k];
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+
+    // Each instance draws the contour through one cell between four texels
+    // at one level.
+    let size = textureDimensions(image);
+    let cells = (size.x - 1u) * (size.y - 1u);
+    let value = level(instance_index / cells);
+    let cell = instance_index % cells;
+    let origin = vec2<u32>(cell % (size.x - 1u), cell / (size.x - 1u));
+
+    var corners = array<vec2<u32>, 4>(
+        origin,
+        origin + vec2<u32>(1u, 0u),
+        origin + vec2<u32>(1u, 1u),
+        origin + vec2<u32>(0u, 1u),
+    );
+    var values: array<f32, 4>;
+    var above = 0u;
+    for (var i = 0u; i < 4u; i++) {
+        values[i] = textureLoad(image, corners[i], 0).r;
+        if (is_nan(values[i])) {
+            return out;
+        }
+        above |= u32(values[i] >= value) << i;
+    }
+
+    let centre = (values[0] + values[1] + values[2] + values[3]) / 4.0;
+    let edges = segment_edges(above, centre >= value, vertex_index / 6u);
+    if (edges.x == NONE) {
+        return out;
+    }
+
+    // Where the level crosses each edge, interpolating between its corners.
+    var ends: array<vec2<f32>, 2>;
+    for (var i = 0u; i < 2u; i++) {
+        let a = edges[i];
+        let b = (a + 1u) % 4u;
+        let t = (value - values[a]) / (values[b] - values[a]);
+        let texel = mix(vec2<f32>(corners[a]), vec2<f32>(corners[b]), t);
+        ends[i] = to_screen(texel_position(texel));
+    }
+    let start = ends[0];
+    let end = ends[1];
+
+    // A quad around the segment, with room for round caps and antialiasing.
+    let delta = end - start;
+    let direction = select(vec2<f32>(1.0, 0.0), normalize(delta), length(delta) > 0.0);
+    let normal = vec2<f32>(-direction.y, direction.x);
+    let half_width = params.thickness / 2.0 + 1.0;
+    var quad = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = quad[vertex_index % 6u];
+    let along = select(start, end, corner.x > 0.0) + direction * corner.x * half_width;
+    let pixel = along + normal * corner.y * half_width;
+
+    out.position = vec4<f32>(pixel / (0.5 * scene.viewport_size), 0.0, 1.0);
+    out.pixel = pixel;
+    out.start = start;
+    out.end = end;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let delta = in.end - in.start;
+    let t = clamp(dot(in.pixel - in.start, delta) / max(dot(delta, delta), 1e-6), 0.0, 1.0);
+    let dist = length(in.pixel - (in.start + delta * t));
+
+    let alpha = clamp(params.thickness / 2.0 + 0.5 - dist, 0.0, 1.0);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(params.colour.rgb, params.colour.a * alpha);
+}
@@ -0,0 +1,191 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{LayerRenderer, SceneParams, colormap_buffer, to_buffer};
+use crate::layer::{Contour, MAX_CONTOUR_LEVELS};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerContourParams {
+    bounds: [f32; 4],
+    colour: [f32; 4],
+    thickness: f32,
+    level_count: u32,
+    _padding: [f32; 2],
+    levels: [f32; MAX_CONTOUR_LEVELS],
+}
+
+fn create_params(layer: &Contour<'_>) -> PerContourParams {
+    let drawn = layer.drawn_levels();
+    let mut levels = [0.; MAX_CONTOUR_LEVELS];
+    levels[..drawn.len()].copy_from_slice(drawn);
+
+    let (thickness, colour) = layer
+        .lines
+        .map_or((0., [0.; 4]), |lines| (lines.thickness, lines.colour));
+
+    PerContourParams {
+        bounds: [
+            layer.bounds.x.min as f32,
+            layer.bounds.y.min as f32,
+            layer.bounds.x.max as f32,
+            layer.bounds.y.max as f32,
+        ],
+        colour,
+        thickness,
+        level_count: drawn.len() as u32,
+        _padding: [0., 0.],
+        levels,
+    }
+}
+
+const IMAGE_ENTRY: wgpu::BindGroupLayoutEntry = wgpu::BindGroupLayoutEntry {
+    binding: 1,
+    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+    ty: wgpu::BindingType::Texture {
+        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+        view_dimension: wgpu::TextureViewDimension::D2,
+        multisampled: false,
+    },
+    count: None,
+};
+
+/// Draws the lines of contours, running marching squares in the vertex shader.
+pub(super) struct LineRenderer {}
+
+impl LayerRenderer for LineRenderer {
+    type Layer<'a> = Contour<'a>;
+    type PerLayerParams = PerContourParams;
+
+    const NAME: &'static str = "contour line";
+
+    const TOPOLOGY: wgpu::PrimitiveTopology = wgpu::PrimitiveTopology::TriangleList;
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl"], "line.wgsl")
+    }
+
+    // Each cell at each level has at most two segments.
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        let cells = data.image.width().saturating_sub(1) * data.image.height().saturating_sub(1);
+        (0..12, 0..cells * data.drawn_levels().len() as u32)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[Self::_WITHOUT_POINTS[0], IMAGE_ENTRY],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        create_params(data)
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: layer.image.as_binding(),
+                },
+            ],
+        })
+    }
+}
+
+/// Fills the bands between contours.
+pub(super) struct FillRenderer {}
+
+impl LayerRenderer for FillRenderer {
+    type Layer<'a> = Contour<'a>;
+    type PerLayerParams = PerContourParams;
+
+    const NAME: &'static str = "contour fill";
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl", "colormap.wgsl"], "fill.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        let drawn =
+            data.image.width() > 1 && data.image.height() > 1 && data.drawn_levels().len() > 1;
+        (0..4, 0..u32::from(drawn))
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITHOUT_POINTS[0],
+                IMAGE_ENTRY,
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        create_params(data)
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+        let colormap = colormap_buffer(device, &name, &layer.fill.unwrap_or_default());
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: layer.image.as_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: colormap.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
@@ -0,0 +1,209 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Point, Rect},
+    peniko::{Color, Fill},
+};
+
+use super::{SceneParams, text::TextLayout};
+use crate::{
+    layer::{Contour, ContourLabels, HorizontalAlign},
+    layout::Padding,
+};
+
+/// Builds a vello scene labelling the levels of `contours` at the current
+/// view. Labels are placed greedily where a contour crosses a cell, skipping
+/// any that would be too close to one already placed.
+///
+/// `scale` is the number of physical pixels per logical pixel.
+pub(super) fn build_scene(
+    contours: &[Contour<'_>],
+    scene_params: &SceneParams,
+    padding: &Padding,
+    scale: f64,
+) -> Scene {
+    let mut scene = Scene::new();
+    // Labels are turned to follow contours on the Cartesian plane only.
+    if scene_params.is_polar() {
+        return scene;
+    }
+
+    let [viewport_width, viewport_height] = scene_params.viewport_size.map(f64::from);
+    let inner = Rect::new(
+        padding.left,
+        padding.top,
+        viewport_width - padding.right,
+        viewport_height - padding.bottom,
+    );
+    let mut placed = Vec::new();
+    for contour in contours {
+        if let Some(labels) = &contour.labels {
+            draw_labels(
+                &mut scene,
+                contour,
+                labels,
+                scene_params,
+                inner,
+                Affine::scale(scale),
+                &mut placed,
+            );
+        }
+    }
+    scene
+}
+
+fn draw_labels(
+    scene: &mut Scene,
+    contour: &Contour<'_>,
+    labels: &ContourLabels<'_>,
+    scene_params: &SceneParams,
+    inner: Rect,
+    transform: Affine,
+    placed: &mut Vec<Point>,
+) {
+    let (width, height) = (contour.image.width(), contour.image.height());
+    if width < 2 || height < 2 {
+        return;
+    }
+    let values = contour.image.values();
+    let value = |i: u32, j: u32| values[(j * width + i) as usize];
+
+    // Values sit at the centres of their cells.
+    let bounds = contour.bounds;
+    let cell = [
+        bounds.x.size() / width as f64,
+        bounds.y.size() / height as f64,
+    ];
+    let to_data = |i: u32, j: u32, corner: Point| {
+        Point::new(
+            bounds.x.min + (i as f64 + 0.5 + corner.x) * cell[0],
+            bounds.y.min + (j as f64 + 0.5 + corner.y) * cell[1],
+        )
+    };
+
+    // Only the cells in view are searched.
+    let to_pixels = scene_params.pixel_transform();
+    let from_pixels = to_pixels.inverse();
+    let corners = [
+        from_pixels * Point::new(inner.x0, inner.y0),
+        from_pixels * Point::new(inner.x1, inner.y1),
+    ];
+    let range = |min: f64, lower: f64, upper: f64, cell: f64, count: u32| {
+        let [a, b] = [lower, upper].map(|v| ((v - min) / cell - 0.5).floor());
+        let last = (count - 2) as f64;
+        (a.min(b).clamp(0., last) as u32)..=(a.max(b).clamp(0., last) as u32)
+    };
+    let columns = range(bounds.x.min, corners[0].x, corners[1].x, cell[0], width);
+    let rows = range(bounds.y.min, corners[0].y, corners[1].y, cell[1], height);
+
+    for &level in contour.drawn_levels() {
+        let label = level.to_string();
+        let label_width = TextLayout::new(&label, labels.font, labels.size).width;
+        let distance = (labels.spacing as f64).max(label_width);
+        for j in rows.clone() {
+            for i in columns.clone() {
+                let corners = [
+                    value(i, j),
+                    value(i + 1, j),
+                    value(i + 1, j + 1),
+                    value(i, j + 1),
+                ];
+                let Some((start, end)) = segment(corners, level) else {
+                    continue;
+                };
+                let start = to_pixels * to_data(i, j, start);
+                let end = to_pixels * to_data(i, j, end);
+                let centre = start.midpoint(end);
+                if !inner.contains(centre)
+                    || placed
+                        .iter()
+                        .any(|other: &Point| other.distance(centre) < distance)
+                {
+                    continue;
+                }
+                placed.push(centre);
+
+                // Keep the text upright.
+                let mut angle = (end - start).atan2();
+                if angle > FRAC_PI_2 {
+                    angle -= PI;
+                } else if angle <= -FRAC_PI_2 {
+                    angle += PI;
+                }
+                let text = TextLayout::new(&label, labels.font, labels.size);
+                let label_transform = transform
+                    * Affine::translate(centre.to_vec2())
+                    * Affine::rotate(angle)
+                    * Affine::translate((-text.width / 2., -text.height / 2.));
+                if let Some(background) = labels.background {
+                    scene.fill(
+                        Fill::NonZero,
+                        label_transform,
+                        Color::new(background),
+                        None,
+                        &Rect::new(0., 0., text.width, text.height),
+                    );
+                }
+                text.draw(
+                    scene,
+                    labels.font,
+                    labels.size,
+                    label_transform,
+                    HorizontalAlign::Left,
+                    labels.colour,
+                );
+            }
+        }
+    }
+}
+
+/// Where `level` crosses a cell with `corners` in anticlockwise order from the
+/// bottom left, as two points in the unit square. Cells the level crosses
+/// twice, or that have a NaN corner, have no single segment.
+fn segment(corners: [f32; 4], level: f32) -> Option<(Point, Point)> {
+    if corners.iter().any(|corner| corner.is_nan()) {
+        return None;
+    }
+
+    const POSITIONS: [Point; 4] = [
+        Point::new(0., 0.),
+        Point::new(1., 0.),
+        Point::new(1., 1.),
+        Point::new(0., 1.),
+    ];
+    let mut crossings = (0..4).filter_map(|k| {
+        let (a, b) = (corners[k], corners[(k + 1) % 4]);
+        ((a < level) != (b < level)).then(|| {
+            let t = ((level - a) / (b - a)) as f64;
+            POSITIONS[k].lerp(POSITIONS[(k + 1) % 4], t)
+        })
+    });
+    match (crossings.next(), crossings.next(), crossings.next()) {
+        (Some(start), Some(end), None) => Some((start, end)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_join_the_crossed_edges() {
+        let (start, end) = segment([0., 1., 1., 0.], 0.5).unwrap();
+        assert_eq!(start, Point::new(0.5, 0.));
+        assert_eq!(end, Point::new(0.5, 1.));
+
+        let (start, end) = segment([0., 0., 4., 0.], 1.).unwrap();
+        assert_eq!(start, Point::new(1., 0.25));
+        assert_eq!(end, Point::new(0.25, 1.));
+    }
+
+    #[test]
+    fn cells_without_one_crossing_have_no_segment() {
+        assert_eq!(segment([0., 0., 0., 0.], 0.5), None);
+        assert_eq!(segment([1., 0., 1., 0.], 0.5), None);
+        assert_eq!(segment([0., f32::NAN, 1., 0.], 0.5), None);
+    }
+}
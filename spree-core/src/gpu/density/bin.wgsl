@@ -0,0 +1,48 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    length: u32,
+    width: u32,
+    height: u32,
+    _pad_0: u32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+// The count in each pixel, row by row from the bottom, followed by the
+// largest count.
+@group(1) @binding(2) var<storage, read_write> counts: array<atomic<u32>>;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_main(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+    // Large buffers are dispatched as a 2D grid of workgroups.
+    let index = id.x + id.y * workgroups.x * WORKGROUP_SIZE;
+    if (index >= params.length || is_gap(points[index])) {
+        return;
+    }
+
+    let ndc = (scene.projection_matrix * vec4<f32>(to_plane(points[index]), 0.0, 1.0)).xy;
+    let pixel = floor((ndc * 0.5 + 0.5) * scene.viewport_size);
+    if (any(pixel < vec2<f32>(0.0)) || any(pixel >= vec2<f32>(f32(params.width), f32(params.height)))) {
+        return;
+    }
+
+    let cell = vec2<u32>(pixel);
+    let count = atomicAdd(&counts[cell.y * params.width + cell.x], 1u) + 1u;
+    // Most points won't raise the maximum, so check before contending for it.
+    let largest = params.width * params.height;
+    if (count > atomicLoad(&counts[largest])) {
+        atomicMax(&counts[largest], count);
+    }
+}
@@ -0,0 +1,184 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{
+    LayerRenderer, SceneParams, colormap_buffer, create_compute_pipeline, to_buffer, workgroups,
+};
+use crate::layer::Density;
+
+const WORKGROUP_SIZE: u32 = 64;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerDensityParams {
+    length: u32,
+    width: u32,
+    height: u32,
+    _padding: u32,
+}
+
+pub(super) struct Renderer {
+    bin_pipeline: wgpu::ComputePipeline,
+    bin_group_0_layout: wgpu::BindGroupLayout,
+    bin_group_1_layout: wgpu::BindGroupLayout,
+}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Density<'a>;
+    type PerLayerParams = PerDensityParams;
+
+    const NAME: &'static str = "density";
+
+    fn new(device: &wgpu::Device) -> Self {
+        let bin_group_0_layout = SceneParams::create_group_layout(device, "density bin");
+        let bin_group_1_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("density bin group 1 layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ..Self::_WITH_POINTS[0]
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ..Self::_WITH_POINTS[1]
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+        let bin_pipeline = create_compute_pipeline(
+            device,
+            "density bin",
+            include_wgsl_with!(["gaps.wgsl", "polar.wgsl"], "bin.wgsl"),
+            &[&bin_group_0_layout, &bin_group_1_layout],
+        );
+
+        Self {
+            bin_pipeline,
+            bin_group_0_layout,
+            bin_group_1_layout,
+        }
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["colormap.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, _: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..4, 0..1)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITH_POINTS[0],
+                Self::_WITH_POINTS[1],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerDensityParams {
+            length: data.data.len() as u32,
+            width: 0,
+            height: 0,
+            _padding: 0,
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        scene_params: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        // Points are counted in each logical pixel of the viewport.
+        let [width, height] = scene_params
+            .viewport_size
+            .map(|size| (size.ceil() as u32).max(1));
+        let params = PerDensityParams {
+            width,
+            height,
+            ..self.create_per_layer_params(layer)
+        };
+        let params_buffer = to_buffer(device, &name, &params);
+        let colormap = colormap_buffer(device, &name, &layer.colormap);
+
+        // Buffers are zeroed on creation, so the counts start empty.
+        let counts = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("density counts"),
+            size: ((width * height + 1) as usize * size_of::<u32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        if !layer.data.is_empty() {
+            let bin_group0 =
+                scene_params.create_bind_group(device, &self.bin_group_0_layout, "density bin");
+            let bin_group1 = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("density bin bind group 1"),
+                layout: &self.bin_group_1_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: layer.data.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: counts.as_entire_binding(),
+                    },
+                ],
+            });
+
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("density bin pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.bin_pipeline);
+            compute_pass.set_bind_group(0, &bin_group0, &[]);
+            compute_pass.set_bind_group(1, &bin_group1, &[]);
+            let (x, y) = workgroups(layer.data.len(), WORKGROUP_SIZE);
+            compute_pass.dispatch_workgroups(x, y, 1);
+        }
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: counts.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: colormap.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
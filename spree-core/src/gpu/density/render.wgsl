@@ -0,0 +1,51 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    length: u32,
+    width: u32,
+    height: u32,
+    _pad_0: u32,
+}
+// The count in each pixel, row by row from the bottom, followed by the
+// largest count.
+@group(1) @binding(0) var<storage, read> counts: array<u32>;
+@group(1) @binding(1) var<uniform> params: Params;
+@group(1) @binding(2) var<storage, read> colormap: array<vec4<f32>>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // The position in logical pixels from the bottom left of the viewport.
+    @location(0) pixel: vec2<f32>,
+}
+
+// Covers the whole viewport.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u));
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.pixel = uv * scene.viewport_size;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let cell = min(vec2<u32>(in.pixel), vec2<u32>(params.width, params.height) - 1u);
+    let count = counts[cell.y * params.width + cell.x];
+    if (count == 0u) {
+        discard;
+    }
+
+    // Counts are compressed logarithmically so that sparse regions remain
+    // visible next to dense ones.
+    let largest = counts[params.width * params.height];
+    return sample_colormap(log(f32(count) + 1.0) / log(f32(largest) + 1.0));
+}
@@ -0,0 +1,92 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{LayerRenderer, SceneParams, to_buffer};
+use crate::layer::ErrorBars;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerErrorBarsParams {
+    colour: [f32; 4],
+    thickness: f32,
+    cap_width: f32,
+    _padding: [f32; 2],
+}
+
+pub(super) struct Renderer {}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = ErrorBars<'a>;
+    type PerLayerParams = PerErrorBarsParams;
+
+    const NAME: &'static str = "error bar";
+
+    const TOPOLOGY: wgpu::PrimitiveTopology = wgpu::PrimitiveTopology::TriangleList;
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl", "polar.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..42, 0..data.data.len().min(data.errors.len()) as u32)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITH_POINTS[0],
+                Self::_WITH_POINTS[1],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerErrorBarsParams {
+            colour: data.colour,
+            thickness: data.thickness,
+            cap_width: data.cap_width,
+            _padding: [0., 0.],
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: layer.data.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: layer.errors.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
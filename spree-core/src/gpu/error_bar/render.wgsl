@@ -0,0 +1,122 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    colour: vec4<f32>,
+    thickness: f32,
+    cap_width: f32,
+    _pad_0: f32,
+    _pad_1: f32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+// The x minus, x plus, y minus and y plus error of each point.
+@group(1) @binding(2) var<storage, read> errors: array<vec4<f32>>;
+
+fn to_screen(point: vec2<f32>) -> vec2<f32> {
+    let ndc = (scene.projection_matrix * vec4<f32>(to_plane(point), 0.0, 1.0)).xy;
+    return ndc * 0.5 * scene.viewport_size;
+}
+
+struct Piece {
+    start: vec2<f32>,
+    end: vec2<f32>,
+}
+
+// The k'th piece of an error bar, in pixels. Pieces are split so they don't
+// overlap, which would show with translucent colours.
+fn piece(k: u32, centre: vec2<f32>, low: vec2<f32>, high: vec2<f32>) -> Piece {
+    let half_width = params.thickness / 2.0;
+    let half_cap = params.cap_width / 2.0;
+    let has_x = high.x > low.x;
+    let has_y = high.y > low.y;
+    let has_caps = params.cap_width > 0.0;
+    // Whiskers stop at the inside edge of their caps.
+    let inset = select(0.0, half_width, has_caps);
+
+    switch k {
+        // The x whisker.
+        case 0u: {
+            if (has_x) {
+                return Piece(vec2<f32>(low.x + inset, centre.y), vec2<f32>(high.x - inset, centre.y));
+            }
+        }
+        // The y whisker, split around the x whisker.
+        case 1u: {
+            if (has_y) {
+                let end = select(high.y - inset, centre.y - half_width, has_x);
+                return Piece(vec2<f32>(centre.x, low.y + inset), vec2<f32>(centre.x, end));
+            }
+        }
+        case 2u: {
+            if (has_y && has_x) {
+                return Piece(vec2<f32>(centre.x, centre.y + half_width), vec2<f32>(centre.x, high.y - inset));
+            }
+        }
+        // The caps.
+        case 3u, 4u: {
+            if (has_x && has_caps) {
+                let x = select(high.x, low.x, k == 3u);
+                return Piece(vec2<f32>(x, centre.y - half_cap), vec2<f32>(x, centre.y + half_cap));
+            }
+        }
+        case 5u, 6u: {
+            if (has_y && has_caps) {
+                let y = select(high.y, low.y, k == 5u);
+                return Piece(vec2<f32>(centre.x - half_cap, y), vec2<f32>(centre.x + half_cap, y));
+            }
+        }
+        default: {}
+    }
+    return Piece(centre, centre);
+}
+
+// Each instance draws the error bar of one point as seven rectangular pieces
+// of six vertices each.
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> @builtin(position) vec4<f32> {
+    let point = points[instance_index];
+    let error = errors[instance_index];
+
+    if (is_gap(point)) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    let centre = to_screen(point);
+    let low = to_screen(point - error.xz);
+    let high = to_screen(point + error.yw);
+    let part = piece(vertex_index / 6u, centre, low, high);
+
+    // Pieces always run from left to right or bottom to top.
+    let delta = part.end - part.start;
+    if (delta.x + delta.y <= 0.0) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    let dir = normalize(delta);
+    let normal = vec2<f32>(-dir.y, dir.x) * params.thickness / 2.0;
+
+    var corners = array<vec2<f32>, 6>(
+        part.start - normal,
+        part.start + normal,
+        part.end - normal,
+        part.end - normal,
+        part.start + normal,
+        part.end + normal,
+    );
+
+    return vec4<f32>(corners[vertex_index % 6u] / (0.5 * scene.viewport_size), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return params.colour;
+}
@@ -0,0 +1,13 @@
+// Prepended to shaders by `include_wgsl_with!`.
+
+// NaN values mark a gap in the data. The bits are compared directly as
+// `x != x` may be optimised away.
+fn is_nan(value: f32) -> bool {
+    return (bitcast<u32>(value) & 0x7fffffffu) > 0x7f800000u;
+}
+
+// Whether a point is a gap, which it is if either coordinate is NaN.
+fn is_gap(point: vec2<f32>) -> bool {
+    let bits = bitcast<vec2<u32>>(point) & vec2<u32>(0x7fffffffu);
+    return any(bits > vec2<u32>(0x7f800000u));
+}
@@ -19,12 +19,12 @@ impl super::LayerRenderer for Renderer {
 
     const NAME: &'static str = "grid";
 
-    fn new() -> Self {
+    fn new(_: &wgpu::Device) -> Self {
         Self {}
     }
 
     fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
-        wgpu::include_wgsl!("render.wgsl")
+        include_wgsl_with!(["polar.wgsl"], "render.wgsl")
     }
 
     fn counts(&self, _: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
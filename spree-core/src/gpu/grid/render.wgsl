@@ -1,6 +1,9 @@
 struct SceneParams {
     projection_matrix: mat4x4<f32>,
     inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
 };
 @group(0) @binding(0) var<uniform> scene: SceneParams;
 
@@ -13,7 +16,8 @@ struct Params {
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
-    @location(0) world_pos: vec2<f32>,
+    // Interpolated on the plane as the polar mapping is not linear.
+    @location(0) plane_pos: vec2<f32>,
 };
 
 @vertex
@@ -25,43 +29,65 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
         vec2<f32>(-1.0,  1.0), vec2<f32>( 1.0, -1.0), vec2<f32>( 1.0,  1.0)
     );
     let ndc = quad_pos[vertex_index];
-    
+
     out.position = vec4<f32>(ndc, 0.0, 1.0);
 
     let unprojected = scene.inverse_projection_matrix * vec4<f32>(ndc, 0.0, 1.0);
-    out.world_pos = unprojected.xy / unprojected.w;
+    out.plane_pos = unprojected.xy / unprojected.w;
 
     return out;
 }
 
+// The distance in plane units from a point at radius `r` to the ray at an
+// angle of `angle` from it.
+fn distance_to_ray(r: f32, angle: f32) -> f32 {
+    return select(r, r * sin(angle), angle < PI / 2.0);
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let world_pos = in.world_pos;
-    let pixel_size = fwidth(world_pos); 
-
-    let axis_thickness = params.axis_thickness; 
-
     let bg_color = vec4<f32>(0.98, 0.98, 0.98, 1.0);
     let grid_color = vec4<f32>(0.85, 0.85, 0.85, 1.0);
     let axis_color = vec4<f32>(0.2, 0.2, 0.2, 1.0);
 
+    // The shortest distances from the current fragment to the nearest grid
+    // line and axis line in physical pixels.
+    var grid_dist: vec2<f32>;
+    var axis_dist: vec2<f32>;
+    if (scene.polar == 0.0) {
+        let world_pos = in.plane_pos;
+        let pixel_size = fwidth(world_pos);
+
+        grid_dist = abs(fract(world_pos / params.spacing + 0.5) - 0.5) * params.spacing / pixel_size;
+        axis_dist = abs(world_pos) / pixel_size;
+    } else {
+        // The spacing is (theta, r), so the grid is made of rays and circles
+        // and the axis is the ray at theta = 0. The plane has the same scale
+        // on both axes.
+        let polar = from_plane(in.plane_pos);
+        let pixel = fwidth(in.plane_pos.x);
+
+        let ray_angle = abs(fract(polar.x / params.spacing.x + 0.5) - 0.5) * params.spacing.x;
+        let circle_dist = abs(fract(polar.y / params.spacing.y + 0.5) - 0.5) * params.spacing.y;
+        grid_dist = vec2<f32>(distance_to_ray(polar.y, ray_angle), circle_dist) / pixel;
+
+        let axis_angle = min(polar.x, TAU - polar.x);
+        axis_dist = vec2<f32>(distance_to_ray(polar.y, axis_angle) / pixel);
+    }
+
     // Grid lines
-    // The shortest distance from the current fragment to the nearest grid line in physical pixels.
-    let grid_dist = abs(fract(world_pos / params.spacing + 0.5) - 0.5) * params.spacing / pixel_size;
     let grid_half_width = vec2<f32>(params.thickness / 2.);
 
     let grid_alphas = 1. - smoothstep(grid_half_width - 0.5, grid_half_width + 0.5, grid_dist);
     let grid_alpha = max(grid_alphas.x, grid_alphas.y);
-    
+
     // Axes
-    // The shortest distance from the current fragment to the nearest axis line in physical pixels.
-    let axis_dist = abs(world_pos) / pixel_size; 
     let axis_half_width = vec2<f32>(params.axis_thickness / 2.);
-    
+
     let axis_alphas = 1. - smoothstep(axis_half_width - 0.5, axis_half_width + 0.5, axis_dist);
     let axis_alpha = max(axis_alphas.x, axis_alphas.y);
 
     // Combine
     var final_color = mix(bg_color, grid_color, grid_alpha);
     return mix(final_color, axis_color, axis_alpha);
-}
\ No newline at end of file
+}
@@ -0,0 +1,107 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{LayerRenderer, SceneParams, colormap_buffer, to_buffer};
+use crate::layer::Heatmap;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerHeatmapParams {
+    nan_colour: [f32; 4],
+    bounds: [f32; 4],
+    range_min: f32,
+    range_max: f32,
+    _padding: [f32; 2],
+}
+
+pub(super) struct Renderer {}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Heatmap<'a>;
+    type PerLayerParams = PerHeatmapParams;
+
+    const NAME: &'static str = "heatmap";
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl", "colormap.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, _: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..4, 0..1)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITHOUT_POINTS[0],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerHeatmapParams {
+            nan_colour: data.nan_colour,
+            bounds: [
+                data.bounds.x.min as f32,
+                data.bounds.y.min as f32,
+                data.bounds.x.max as f32,
+                data.bounds.y.max as f32,
+            ],
+            range_min: data.range.min as f32,
+            range_max: data.range.max as f32,
+            _padding: [0., 0.],
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+        let colormap = colormap_buffer(device, &name, &layer.colormap);
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: layer.image.as_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: colormap.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
@@ -0,0 +1,53 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    nan_colour: vec4<f32>,
+    // The minimum x and y, then the maximum x and y, of the image in data
+    // coordinates.
+    bounds: vec4<f32>,
+    range_min: f32,
+    range_max: f32,
+    _pad_0: f32,
+    _pad_1: f32,
+}
+@group(1) @binding(0) var<uniform> params: Params;
+@group(1) @binding(1) var image: texture_2d<f32>;
+@group(1) @binding(2) var<storage, read> colormap: array<vec4<f32>>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // The position within the image, from zero to one.
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u));
+    let point = mix(params.bounds.xy, params.bounds.zw, uv);
+
+    var out: VertexOutput;
+    out.position = scene.projection_matrix * vec4<f32>(point, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = textureDimensions(image);
+    let cell = min(vec2<u32>(in.uv * vec2<f32>(size)), size - 1u);
+    let value = textureLoad(image, cell, 0).r;
+    if (is_nan(value)) {
+        return params.nan_colour;
+    }
+
+    // An empty range maps every value to the start of the colormap.
+    let span = params.range_max - params.range_min;
+    return sample_colormap(select(0.0, (value - params.range_min) / span, span != 0.0));
+}
@@ -0,0 +1,44 @@
+struct Params {
+    colour: vec4<f32>,
+    range_min: f32,
+    range_max: f32,
+    bins: u32,
+    length: u32,
+    axis: u32,
+    orientation: u32,
+    density: u32,
+    _pad_0: u32,
+}
+@group(0) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(0) @binding(1) var<uniform> params: Params;
+// The count in each bin, followed by the total count across all bins.
+@group(0) @binding(2) var<storage, read_write> counts: array<atomic<u32>>;
+
+const AXIS_X: u32 = 0u;
+const AXIS_Y: u32 = 1u;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_main(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+    // Large buffers are dispatched as a 2D grid of workgroups.
+    let index = id.x + id.y * workgroups.x * WORKGROUP_SIZE;
+    if (index >= params.length || is_gap(points[index])) {
+        return;
+    }
+
+    let value = select(points[index].x, points[index].y, params.axis == AXIS_Y);
+    if (value < params.range_min || value > params.range_max) {
+        return;
+    }
+
+    // An empty range puts every value in the first bin.
+    let span = params.range_max - params.range_min;
+    let fraction = select(0.0, (value - params.range_min) / span, span != 0.0);
+    let bin = min(u32(fraction * f32(params.bins)), params.bins - 1u);
+    atomicAdd(&counts[bin], 1u);
+    atomicAdd(&counts[params.bins], 1u);
+}
@@ -0,0 +1,164 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{LayerRenderer, SceneParams, create_compute_pipeline, to_buffer, workgroups};
+use crate::layer::{Axis, Histogram, Orientation};
+
+const WORKGROUP_SIZE: u32 = 64;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerHistogramParams {
+    colour: [f32; 4],
+    range_min: f32,
+    range_max: f32,
+    bins: u32,
+    length: u32,
+    axis: u32,
+    orientation: u32,
+    density: u32,
+    _padding: u32,
+}
+
+pub(super) struct Renderer {
+    bin_pipeline: wgpu::ComputePipeline,
+    bin_group_layout: wgpu::BindGroupLayout,
+}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Histogram<'a>;
+    type PerLayerParams = PerHistogramParams;
+
+    const NAME: &'static str = "histogram";
+
+    fn new(device: &wgpu::Device) -> Self {
+        let bin_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("histogram bin group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ..Self::_WITH_POINTS[0]
+                },
+                wgpu::BindGroupLayoutEntry {
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ..Self::_WITH_POINTS[1]
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let bin_pipeline = create_compute_pipeline(
+            device,
+            "histogram bin",
+            include_wgsl_with!(["gaps.wgsl"], "bin.wgsl"),
+            &[&bin_group_layout],
+        );
+
+        Self {
+            bin_pipeline,
+            bin_group_layout,
+        }
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["polar.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..4, 0..data.bins.get())
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerHistogramParams {
+            colour: data.colour,
+            range_min: data.range.min as f32,
+            range_max: data.range.max as f32,
+            bins: data.bins.get(),
+            length: data.data.len() as u32,
+            axis: match data.axis {
+                Axis::X => 0,
+                Axis::Y => 1,
+            },
+            orientation: match data.orientation {
+                Orientation::Vertical => 0,
+                Orientation::Horizontal => 1,
+            },
+            density: data.density as u32,
+            _padding: 0,
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+
+        // Buffers are zeroed on creation, so the counts start empty.
+        let counts = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("histogram counts"),
+            size: ((layer.bins.get() as usize + 1) * size_of::<u32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        if !layer.data.is_empty() {
+            let bin_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("histogram bin bind group"),
+                layout: &self.bin_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: layer.data.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: counts.as_entire_binding(),
+                    },
+                ],
+            });
+
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("histogram bin pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.bin_pipeline);
+            compute_pass.set_bind_group(0, &bin_group, &[]);
+            let (x, y) = workgroups(layer.data.len(), WORKGROUP_SIZE);
+            compute_pass.dispatch_workgroups(x, y, 1);
+        }
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: counts.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
@@ -0,0 +1,62 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+const ORIENTATION_VERTICAL: u32 = 0u;
+const ORIENTATION_HORIZONTAL: u32 = 1u;
+
+struct Params {
+    colour: vec4<f32>,
+    range_min: f32,
+    range_max: f32,
+    bins: u32,
+    length: u32,
+    axis: u32,
+    orientation: u32,
+    density: u32,
+    _pad_0: u32,
+}
+// The count in each bin, followed by the total count across all bins.
+@group(1) @binding(0) var<storage, read> counts: array<u32>;
+@group(1) @binding(1) var<uniform> params: Params;
+
+// Each instance draws the bar for one bin.
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> @builtin(position) vec4<f32> {
+    let bin_width = (params.range_max - params.range_min) / f32(params.bins);
+    let start = params.range_min + f32(instance_index) * bin_width;
+
+    var height = f32(counts[instance_index]);
+    if (params.density == 1u) {
+        let total = f32(counts[params.bins]);
+        // Bars over an empty range have no width, so no density either.
+        height = select(height / (total * bin_width), 0.0, total == 0.0 || bin_width == 0.0);
+    }
+
+    var corners = array<vec2<f32>, 4>(
+        vec2<f32>(start, 0.0),
+        vec2<f32>(start + bin_width, 0.0),
+        vec2<f32>(start, height),
+        vec2<f32>(start + bin_width, height),
+    );
+
+    var corner = corners[vertex_index];
+    if (params.orientation == ORIENTATION_HORIZONTAL) {
+        corner = corner.yx;
+    }
+
+    return scene.projection_matrix * vec4<f32>(to_plane(corner), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return params.colour;
+}
@@ -0,0 +1,51 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    // The width of each bin in logical pixels.
+    size: f32,
+    shape: u32,
+    columns: u32,
+    rows: u32,
+    length: u32,
+    log: u32,
+    _pad_0: u32,
+    _pad_1: u32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+// The count in each bin, followed by the largest count.
+@group(1) @binding(2) var<storage, read_write> counts: array<atomic<u32>>;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_main(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+    // Large buffers are dispatched as a 2D grid of workgroups.
+    let index = id.x + id.y * workgroups.x * WORKGROUP_SIZE;
+    if (index >= params.length || is_gap(points[index])) {
+        return;
+    }
+
+    let ndc = (scene.projection_matrix * vec4<f32>(points[index], 0.0, 1.0)).xy;
+    let bin = bin_index((ndc * 0.5 + 0.5) * scene.viewport_size);
+    if (bin < 0) {
+        return;
+    }
+
+    let count = atomicAdd(&counts[bin], 1u) + 1u;
+    // Most points won't raise the maximum, so check before contending for it.
+    let largest = arrayLength(&counts) - 1u;
+    if (count > atomicLoad(&counts[largest])) {
+        atomicMax(&counts[largest], count);
+    }
+}
@@ -0,0 +1,50 @@
+// Prepended to the 2d histogram shaders by `include_wgsl_with!`, so that
+// binning and drawing agree on which bin a pixel is in. Expects the shader to
+// bind `scene` and `params`. `bin_index` in mod.rs must match.
+
+const SHAPE_RECTANGLE: u32 = 0u;
+const SHAPE_HEXAGON: u32 = 1u;
+
+// The spacing between bin centres in logical pixels. Hexagonal bins are
+// centred on two rectangular lattices, the second offset by half the spacing.
+fn spacing() -> vec2<f32> {
+    if (params.shape == SHAPE_HEXAGON) {
+        return params.size * vec2<f32>(1.0, sqrt(3.0));
+    }
+    return vec2<f32>(params.size);
+}
+
+// Bins are anchored to the data origin so that they stay put while panning.
+fn origin() -> vec2<f32> {
+    let ndc = (scene.projection_matrix * vec4<f32>(0.0, 0.0, 0.0, 1.0)).xy;
+    return (ndc * 0.5 + 0.5) * scene.viewport_size;
+}
+
+// The index into `counts` of the bin containing a position in logical pixels
+// from the bottom left of the viewport, or -1 if it isn't near the viewport.
+fn bin_index(pixel: vec2<f32>) -> i32 {
+    let cell_size = spacing();
+    let position = (pixel - origin()) / cell_size;
+
+    var cell = vec2<i32>(floor(position));
+    var lattice = 0;
+    if (params.shape == SHAPE_HEXAGON) {
+        let nearest = round(position);
+        let offset = floor(position) + 0.5;
+        if (length((position - nearest) * cell_size) <= length((position - offset) * cell_size)) {
+            cell = vec2<i32>(nearest);
+        } else {
+            lattice = 1;
+        }
+    }
+
+    let first = vec2<i32>(floor(-origin() / cell_size)) - 1;
+    let local = cell - first;
+    let size = vec2<i32>(i32(params.columns), i32(params.rows));
+    if (any(local < vec2<i32>(0)) || any(local >= size)) {
+        return -1;
+    }
+
+    let lattices = select(1, 2, params.shape == SHAPE_HEXAGON);
+    return (local.y * size.x + local.x) * lattices + lattice;
+}
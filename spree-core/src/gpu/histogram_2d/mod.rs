@@ -0,0 +1,398 @@
+use std::cell::RefCell;
+
+use bytemuck::{Pod, Zeroable};
+use vello::{kurbo::Point, wgpu};
+
+use super::{
+    LayerRenderer, SceneParams, colormap_buffer, create_compute_pipeline, readback::Readback,
+    to_buffer, workgroups,
+};
+use crate::layer::{BinShape, Histogram2d};
+
+const WORKGROUP_SIZE: u32 = 64;
+const SHAPE_RECTANGLE: u32 = 0;
+const SHAPE_HEXAGON: u32 = 1;
+/// The smallest bin width in logical pixels. Smaller bins, along with zero,
+/// negative and NaN sizes, are drawn this size instead.
+const MIN_SIZE: f32 = 1.;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerHistogram2dParams {
+    size: f32,
+    shape: u32,
+    columns: u32,
+    rows: u32,
+    length: u32,
+    log: u32,
+    _padding: [u32; 2],
+}
+
+pub(super) struct Renderer {
+    bin_pipeline: wgpu::ComputePipeline,
+    bin_group_0_layout: wgpu::BindGroupLayout,
+    bin_group_1_layout: wgpu::BindGroupLayout,
+    probe: RefCell<Probe>,
+}
+
+/// Reads back the count of the bin under the mouse for tooltips.
+struct Probe {
+    /// The mouse position in logical pixels from the top left.
+    cursor: Option<Point>,
+    readback: Readback,
+    /// Where the mouse was when the copy being read back was recorded.
+    recorded_at: Option<Point>,
+    /// The last count read back and where the mouse was for it.
+    count: Option<(u32, Point)>,
+    /// Whether a layer this frame has a tooltip.
+    enabled: bool,
+    /// Whether the mouse is over a bin of a layer with a tooltip this frame.
+    over_bin: bool,
+}
+
+impl Renderer {
+    /// Picks up the count read back since the last frame and moves the probe
+    /// to `cursor`, in logical pixels from the top left.
+    pub(super) fn start_frame(&self, cursor: Option<Point>) {
+        let probe = &mut *self.probe.borrow_mut();
+        if let Some(count) = probe
+            .readback
+            .read(|bytes| bytemuck::cast_slice::<u8, u32>(bytes)[0])
+        {
+            probe.count = probe.recorded_at.map(|at| (count, at));
+        }
+        probe.cursor = cursor;
+        probe.enabled = false;
+        probe.over_bin = false;
+    }
+
+    /// The count to show in a tooltip this frame, which may be for where the
+    /// mouse was a frame or two ago.
+    pub(super) fn tooltip_count(&self) -> Option<u32> {
+        let probe = self.probe.borrow();
+        probe.over_bin.then_some(probe.count?.0)
+    }
+
+    /// Whether a layer had a tooltip last frame, so moving the mouse needs a
+    /// redraw.
+    pub(super) fn has_tooltips(&self) -> bool {
+        self.probe.borrow().enabled
+    }
+
+    /// Whether the tooltip is out of date, so another frame is needed once
+    /// the count is read back.
+    pub(super) fn needs_redraw(&self) -> bool {
+        let probe = self.probe.borrow();
+        probe.over_bin && probe.count.map(|(_, at)| at) != probe.cursor
+    }
+
+    /// Starts reading back the count if one was recorded. This must be called
+    /// after the frame's commands are submitted.
+    pub(super) fn after_submit(&self) {
+        self.probe.borrow_mut().readback.after_submit();
+    }
+}
+
+impl Probe {
+    /// Records copying the count of the bin under the mouse from `counts`.
+    fn record(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        counts: &wgpu::Buffer,
+        params: &PerHistogram2dParams,
+        scene_params: &SceneParams,
+    ) {
+        self.enabled = true;
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        let [width, height] = scene_params.viewport_size;
+        let pixel = [cursor.x as f32, height - cursor.y as f32];
+        let matrix = scene_params.projection_matrix;
+        let origin = [0, 1].map(|i| (matrix[3][i] * 0.5 + 0.5) * [width, height][i]);
+        let Some(bin) = bin_index(params, origin, pixel) else {
+            self.over_bin = false;
+            return;
+        };
+
+        self.over_bin = true;
+        if self.readback.is_free() {
+            let size = size_of::<u32>() as u64;
+            self.readback
+                .record(encoder, counts, bin as u64 * size, size);
+            self.recorded_at = Some(cursor);
+        }
+    }
+}
+
+/// The index into the counts of the bin containing `pixel`, given in logical
+/// pixels from the bottom left of the viewport, with the data origin at
+/// `origin`. This must match `bin_index` in `bin_index.wgsl`, which the
+/// shaders share, as only this copy is tested.
+fn bin_index(params: &PerHistogram2dParams, origin: [f32; 2], pixel: [f32; 2]) -> Option<u32> {
+    let hexagon = params.shape == SHAPE_HEXAGON;
+    let spacing = if hexagon {
+        [params.size, params.size * 3f32.sqrt()]
+    } else {
+        [params.size; 2]
+    };
+    let position = [0, 1].map(|i| (pixel[i] - origin[i]) / spacing[i]);
+
+    let mut cell = position.map(f32::floor);
+    let mut lattice = 0;
+    if hexagon {
+        let nearest = position.map(f32::round_ties_even);
+        let offset = position.map(|p| p.floor() + 0.5);
+        let distance = |centre: [f32; 2]| {
+            let [x, y] = [0, 1].map(|i| (position[i] - centre[i]) * spacing[i]);
+            x.hypot(y)
+        };
+        if distance(nearest) <= distance(offset) {
+            cell = nearest;
+        } else {
+            lattice = 1;
+        }
+    }
+
+    let [x, y] = [0, 1].map(|i| cell[i] - ((-origin[i] / spacing[i]).floor() - 1.));
+    if x < 0. || y < 0. || x >= params.columns as f32 || y >= params.rows as f32 {
+        return None;
+    }
+    let lattices = if hexagon { 2 } else { 1 };
+    Some((y as u32 * params.columns + x as u32) * lattices + lattice)
+}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Histogram2d<'a>;
+    type PerLayerParams = PerHistogram2dParams;
+
+    const NAME: &'static str = "2d histogram";
+
+    fn new(device: &wgpu::Device) -> Self {
+        let bin_group_0_layout = SceneParams::create_group_layout(device, "2d histogram bin");
+        let bin_group_1_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("2d histogram bin group 1 layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ..Self::_WITH_POINTS[0]
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ..Self::_WITH_POINTS[1]
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+        let bin_pipeline = create_compute_pipeline(
+            device,
+            "2d histogram bin",
+            include_wgsl_with!(["gaps.wgsl", "histogram_2d/bin_index.wgsl"], "bin.wgsl"),
+            &[&bin_group_0_layout, &bin_group_1_layout],
+        );
+
+        Self {
+            bin_pipeline,
+            bin_group_0_layout,
+            bin_group_1_layout,
+            probe: RefCell::new(Probe {
+                cursor: None,
+                readback: Readback::new(device, "2d histogram count readback", 4),
+                recorded_at: None,
+                count: None,
+                enabled: false,
+                over_bin: false,
+            }),
+        }
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(
+            ["colormap.wgsl", "histogram_2d/bin_index.wgsl"],
+            "render.wgsl"
+        )
+    }
+
+    fn counts(&self, _: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..4, 0..1)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITH_POINTS[0],
+                Self::_WITH_POINTS[1],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerHistogram2dParams {
+            size: data.size.max(MIN_SIZE),
+            shape: match data.shape {
+                BinShape::Rectangle => SHAPE_RECTANGLE,
+                BinShape::Hexagon => SHAPE_HEXAGON,
+            },
+            columns: 0,
+            rows: 0,
+            length: data.data.len() as u32,
+            log: data.log as u32,
+            _padding: [0, 0],
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        scene_params: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        // Only the bins around the viewport are counted. This must match the
+        // spacing of the bins in the shaders, with a margin for the bins that
+        // are partly visible.
+        let params = self.create_per_layer_params(layer);
+        let (spacing, lattices) = match layer.shape {
+            BinShape::Rectangle => ([params.size; 2], 1),
+            BinShape::Hexagon => ([params.size, params.size * 3f32.sqrt()], 2),
+        };
+        let [columns, rows] =
+            [0, 1].map(|i| (scene_params.viewport_size[i] / spacing[i]).ceil() as u32 + 3);
+        let params = PerHistogram2dParams {
+            columns,
+            rows,
+            ..params
+        };
+        let params_buffer = to_buffer(device, &name, &params);
+        let colormap = colormap_buffer(device, &name, &layer.colormap);
+
+        // Buffers are zeroed on creation, so the counts start empty.
+        let counts = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("2d histogram counts"),
+            size: ((columns * rows * lattices + 1) as usize * size_of::<u32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        if !layer.data.is_empty() {
+            let bin_group0 = scene_params.create_bind_group(
+                device,
+                &self.bin_group_0_layout,
+                "2d histogram bin",
+            );
+            let bin_group1 = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("2d histogram bin bind group 1"),
+                layout: &self.bin_group_1_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: layer.data.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: counts.as_entire_binding(),
+                    },
+                ],
+            });
+
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("2d histogram bin pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.bin_pipeline);
+            compute_pass.set_bind_group(0, &bin_group0, &[]);
+            compute_pass.set_bind_group(1, &bin_group1, &[]);
+            let (x, y) = workgroups(layer.data.len(), WORKGROUP_SIZE);
+            compute_pass.dispatch_workgroups(x, y, 1);
+        }
+        if layer.tooltip.is_some() {
+            self.probe
+                .borrow_mut()
+                .record(encoder, &counts, &params, scene_params);
+        }
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: counts.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: colormap.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(shape: u32) -> PerHistogram2dParams {
+        PerHistogram2dParams {
+            size: 10.,
+            shape,
+            columns: 5,
+            rows: 5,
+            length: 0,
+            log: 0,
+            _padding: [0, 0],
+        }
+    }
+
+    #[test]
+    fn rectangular_bins_count_from_the_first_column() {
+        let params = params(SHAPE_RECTANGLE);
+        // The first bins are one to the left of and below the origin.
+        assert_eq!(bin_index(&params, [0., 0.], [15., 25.]), Some(17));
+        assert_eq!(bin_index(&params, [0., 0.], [35., 5.]), Some(9));
+        assert_eq!(bin_index(&params, [0., 0.], [-5., -5.]), Some(0));
+        assert_eq!(bin_index(&params, [25., 0.], [0., 5.]), Some(6));
+    }
+
+    #[test]
+    fn positions_outside_the_grid_have_no_bin() {
+        let params = params(SHAPE_RECTANGLE);
+        assert_eq!(bin_index(&params, [0., 0.], [-15., 5.]), None);
+        assert_eq!(bin_index(&params, [0., 0.], [45., 5.]), None);
+        assert_eq!(bin_index(&params, [0., 0.], [5., 45.]), None);
+    }
+
+    #[test]
+    fn hexagonal_bins_pick_the_nearest_lattice() {
+        let params = params(SHAPE_HEXAGON);
+        let height = 10. * 3f32.sqrt();
+        assert_eq!(bin_index(&params, [0., 0.], [1., 1.]), Some(12));
+        assert_eq!(bin_index(&params, [0., 0.], [5., height / 2.]), Some(13));
+        assert_eq!(bin_index(&params, [0., 0.], [9., height - 1.]), Some(24));
+    }
+}
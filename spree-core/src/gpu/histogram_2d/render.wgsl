@@ -0,0 +1,56 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    // The width of each bin in logical pixels.
+    size: f32,
+    shape: u32,
+    columns: u32,
+    rows: u32,
+    length: u32,
+    log: u32,
+    _pad_0: u32,
+    _pad_1: u32,
+}
+// The count in each bin, followed by the largest count.
+@group(1) @binding(0) var<storage, read> counts: array<u32>;
+@group(1) @binding(1) var<uniform> params: Params;
+@group(1) @binding(2) var<storage, read> colormap: array<vec4<f32>>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // The position in logical pixels from the bottom left of the viewport.
+    @location(0) pixel: vec2<f32>,
+}
+
+// Covers the whole viewport.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u));
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.pixel = uv * scene.viewport_size;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let bin = bin_index(in.pixel);
+    if (bin < 0 || counts[bin] == 0u) {
+        discard;
+    }
+
+    let count = f32(counts[bin]);
+    let largest = f32(counts[arrayLength(&counts) - 1u]);
+    if (params.log == 1u) {
+        return sample_colormap(log(count + 1.0) / log(largest + 1.0));
+    }
+    return sample_colormap(count / largest);
+}
@@ -0,0 +1,47 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    // Where the legend would be in each corner, as the left, top, right and
+    // bottom edges in pixels with y down.
+    rects: array<vec4<f32>, 4>,
+    length: u32,
+    _pad_0: u32,
+    _pad_1: u32,
+    _pad_2: u32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+// The number of points under the legend in each corner.
+@group(1) @binding(2) var<storage, read_write> counts: array<atomic<u32>, 4>;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_main(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+    // Large buffers are dispatched as a 2D grid of workgroups.
+    let index = id.x + id.y * workgroups.x * WORKGROUP_SIZE;
+    if (index >= params.length || is_gap(points[index])) {
+        return;
+    }
+
+    let clip = scene.projection_matrix * vec4<f32>(to_plane(points[index]), 0.0, 1.0);
+    let ndc = clip.xy / clip.w;
+    let pixel = vec2<f32>(ndc.x + 1.0, 1.0 - ndc.y) * 0.5 * scene.viewport_size;
+
+    for (var i = 0u; i < 4u; i++) {
+        let rect = params.rects[i];
+        if (all(pixel >= rect.xy) && all(pixel <= rect.zw)) {
+            atomicAdd(&counts[i], 1u);
+        }
+    }
+}
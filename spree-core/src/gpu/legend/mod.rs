@@ -0,0 +1,543 @@
+use std::{collections::HashSet, f64::consts::PI};
+
+use bytemuck::{Pod, Zeroable};
+use vello::{
+    Scene,
+    kurbo::{self, Affine, BezPath, Circle, Line as Segment, Point, Rect, Shape, Stroke},
+    peniko::{Color, Fill},
+    wgpu,
+};
+
+use super::{
+    SceneParams, create_compute_pipeline, readback::Readback, text::TextLayout, to_buffer,
+    workgroups,
+};
+use crate::{
+    layer::{
+        HorizontalAlign, Layer, Legend, LegendPlacement, Line, LineCap, Marker, PointBuffer,
+        Scatter, Side,
+    },
+    layout::PlotInstanceLayout,
+};
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// The gap between the legend and the edge of the plot in pixels.
+const MARGIN: f64 = 10.;
+
+/// How opaque the entries of hidden series are drawn.
+const HIDDEN_ALPHA: f32 = 0.3;
+
+/// The inside corners in the order [`LegendPlacement::Best`] prefers them.
+const CORNERS: [LegendPlacement; 4] = [
+    LegendPlacement::TopRight,
+    LegendPlacement::TopLeft,
+    LegendPlacement::BottomRight,
+    LegendPlacement::BottomLeft,
+];
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+struct CountParams {
+    rects: [[f32; 4]; 4],
+    length: u32,
+    _padding: [u32; 3],
+}
+
+/// A labelled series, drawn with every line and scatter that shares its
+/// label.
+#[derive(Clone, Copy)]
+struct Entry<'a> {
+    label: &'a str,
+    line: Option<Line<'a>>,
+    scatter: Option<Scatter<'a>>,
+}
+
+/// The labelled series of a frame, and the points of every visible line and
+/// scatter with the y axis they are drawn against.
+pub(super) struct Series<'a> {
+    entries: Vec<Entry<'a>>,
+    points: Vec<(&'a PointBuffer, Side)>,
+}
+
+/// Which series are hidden, and where their entries were last drawn.
+pub(super) struct State {
+    hidden: HashSet<String>,
+    /// The area of each entry in physical pixels.
+    hit_boxes: Vec<(Rect, String)>,
+
+    count_pipeline: wgpu::ComputePipeline,
+    count_group_0_layout: wgpu::BindGroupLayout,
+    count_group_1_layout: wgpu::BindGroupLayout,
+    counts: wgpu::Buffer,
+    readback: Readback,
+    /// The index of the best corner in `CORNERS`.
+    best: usize,
+}
+
+impl<'a> Series<'a> {
+    pub(super) fn new(layers: &[Layer<'a>], state: &State) -> Self {
+        let mut series = Self {
+            entries: vec![],
+            points: vec![],
+        };
+
+        for layer in layers {
+            match layer {
+                Layer::Lines(lines) => {
+                    for line in lines {
+                        if let Some(label) = line.label {
+                            series.entry(label).line.get_or_insert(*line);
+                        }
+                        if !state.is_hidden(line.label) {
+                            series.points.push((line.data, line.y_axis));
+                        }
+                    }
+                }
+                Layer::Scatters(scatters) => {
+                    for scatter in scatters {
+                        if let Some(label) = scatter.label {
+                            series.entry(label).scatter.get_or_insert(*scatter);
+                        }
+                        if !state.is_hidden(scatter.label) {
+                            series.points.push((scatter.data, scatter.y_axis));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        series
+    }
+
+    fn entry(&mut self, label: &'a str) -> &mut Entry<'a> {
+        let index = match self.entries.iter().position(|entry| entry.label == label) {
+            Some(index) => index,
+            None => {
+                self.entries.push(Entry {
+                    label,
+                    line: None,
+                    scatter: None,
+                });
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[index]
+    }
+}
+
+impl State {
+    pub(super) fn new(device: &wgpu::Device) -> Self {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let count_group_0_layout = SceneParams::create_group_layout(device, "legend count");
+        let count_group_1_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("legend count group 1 layout"),
+                entries: &[
+                    storage(0, true),
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    storage(2, false),
+                ],
+            });
+        let count_pipeline = create_compute_pipeline(
+            device,
+            "legend count",
+            include_wgsl_with!(["gaps.wgsl", "polar.wgsl"], "count.wgsl"),
+            &[&count_group_0_layout, &count_group_1_layout],
+        );
+
+        let size = (CORNERS.len() * size_of::<u32>()) as u64;
+        let counts = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("legend counts"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let readback = Readback::new(device, "legend counts readback", size);
+
+        Self {
+            hidden: HashSet::new(),
+            hit_boxes: vec![],
+            count_pipeline,
+            count_group_0_layout,
+            count_group_1_layout,
+            counts,
+            readback,
+            best: 0,
+        }
+    }
+
+    fn is_hidden(&self, label: Option<&str>) -> bool {
+        label.is_some_and(|label| self.hidden.contains(label))
+    }
+
+    /// Removes the lines and scatters of hidden series from `layers`.
+    pub(super) fn hide(&self, layers: &mut [Layer<'_>]) {
+        for layer in layers {
+            match layer {
+                Layer::Lines(lines) => lines.retain(|line| !self.is_hidden(line.label)),
+                Layer::Scatters(scatters) => {
+                    scatters.retain(|scatter| !self.is_hidden(scatter.label))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Forgets where entries were drawn, before drawing a new frame that may
+    /// not have a legend.
+    pub(super) fn clear_hit_boxes(&mut self) {
+        self.hit_boxes.clear();
+    }
+
+    /// Hides or shows the series whose entry is at `position`, in physical
+    /// pixels. Returns whether there was an entry there.
+    pub(super) fn toggle(&mut self, position: Point) -> bool {
+        let Some((_, label)) = self
+            .hit_boxes
+            .iter()
+            .find(|(rect, _)| rect.contains(position))
+        else {
+            return false;
+        };
+
+        if !self.hidden.remove(label) {
+            self.hidden.insert(label.clone());
+        }
+        true
+    }
+
+    /// Builds a vello scene drawing the legend.
+    ///
+    /// `scale` is the number of physical pixels per logical pixel.
+    pub(super) fn build_scene(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        legend: &Legend<'_>,
+        series: &Series<'_>,
+        layout: &PlotInstanceLayout,
+        scale: f64,
+    ) -> Scene {
+        let mut scene = Scene::new();
+        if series.entries.is_empty() {
+            return scene;
+        }
+
+        let size = legend.size as f64;
+        let spacing = size / 2.;
+        let swatch_width = size * 2.;
+        let rows: Vec<_> = series
+            .entries
+            .iter()
+            .map(|entry| TextLayout::new(entry.label, legend.font, legend.size))
+            .collect();
+        let text_width = rows.iter().map(|text| text.width).fold(0., f64::max);
+        let width = spacing * 3. + swatch_width + text_width;
+        let height = spacing * 2. + rows.iter().map(|text| text.height.max(size)).sum::<f64>();
+
+        let inner = Rect::new(
+            layout.padding.left,
+            layout.padding.top,
+            layout.logical_width - layout.padding.right,
+            layout.logical_height - layout.padding.bottom,
+        );
+        let placement = match legend.placement {
+            LegendPlacement::Best => {
+                let rects = CORNERS.map(|corner| place(corner, inner, width, height));
+                self.best_corner(device, encoder, layout, &series.points, rects)
+            }
+            placement => placement,
+        };
+        let rect = place(placement, inner, width, height);
+        let transform = Affine::scale(scale) * Affine::translate(rect.origin().to_vec2());
+
+        if let Some(background) = legend.background {
+            let rect = Rect::new(0., 0., width, height);
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                Color::new(background),
+                None,
+                &rect,
+            );
+        }
+
+        let mut y = spacing;
+        for (entry, text) in series.entries.iter().zip(rows) {
+            let row_height = text.height.max(size);
+            let alpha = if self.hidden.contains(entry.label) {
+                HIDDEN_ALPHA
+            } else {
+                1.
+            };
+            let centre = Point::new(spacing + swatch_width / 2., y + row_height / 2.);
+
+            if let Some(line) = entry.line {
+                draw_line(
+                    &mut scene,
+                    transform,
+                    &line,
+                    centre,
+                    swatch_width,
+                    size,
+                    alpha,
+                );
+            }
+            if let Some(scatter) = entry.scatter {
+                draw_marker(&mut scene, transform, &scatter, centre, size, alpha);
+            }
+
+            let [r, g, b, a] = legend.colour;
+            text.draw(
+                &mut scene,
+                legend.font,
+                legend.size,
+                transform * Affine::translate((spacing * 2. + swatch_width, y)),
+                HorizontalAlign::Left,
+                [r, g, b, a * alpha],
+            );
+
+            let row = Rect::new(0., y, width, y + row_height) + rect.origin().to_vec2();
+            self.hit_boxes
+                .push((row.scale_from_origin(scale), entry.label.to_owned()));
+            y += row_height;
+        }
+
+        scene
+    }
+
+    /// Returns the corner whose legend covered the fewest points when last
+    /// counted, and records counting them again if the last count has been
+    /// read.
+    fn best_corner(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        layout: &PlotInstanceLayout,
+        points: &[(&PointBuffer, Side)],
+        rects: [Rect; 4],
+    ) -> LegendPlacement {
+        if let Some(best) = self.readback.read(|bytes| {
+            let counts: &[u32] = bytemuck::cast_slice(bytes);
+            // Ties go to the earlier, more preferred, corner.
+            (0..CORNERS.len())
+                .min_by_key(|&i| counts[i])
+                .unwrap_or_default()
+        }) {
+            self.best = best;
+        }
+
+        if self.readback.is_free() {
+            encoder.clear_buffer(&self.counts, 0, None);
+
+            let rects = rects.map(|rect| [rect.x0, rect.y0, rect.x1, rect.y1].map(|v| v as f32));
+            for &(points, side) in points.iter().filter(|(points, _)| !points.is_empty()) {
+                // Points are counted where they are drawn, against their y axis.
+                let group0 = layout.scene_params_for(side).create_bind_group(
+                    device,
+                    &self.count_group_0_layout,
+                    "legend count",
+                );
+                let params = CountParams {
+                    rects,
+                    length: points.len() as u32,
+                    _padding: [0; 3],
+                };
+                let params_buffer = to_buffer(device, "legend count", &params);
+                let group1 = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("legend count bind group 1"),
+                    layout: &self.count_group_1_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: points.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: params_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: self.counts.as_entire_binding(),
+                        },
+                    ],
+                });
+
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("legend count pass"),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&self.count_pipeline);
+                pass.set_bind_group(0, &group0, &[]);
+                pass.set_bind_group(1, &group1, &[]);
+                let (x, y) = workgroups(points.len(), WORKGROUP_SIZE);
+                pass.dispatch_workgroups(x, y, 1);
+            }
+
+            self.readback
+                .record(encoder, &self.counts, 0, self.counts.size());
+        }
+
+        CORNERS[self.best]
+    }
+
+    /// Starts reading back the counts recorded this frame. This must be called
+    /// after the frame's commands are submitted.
+    pub(super) fn after_submit(&mut self) {
+        self.readback.after_submit();
+    }
+}
+
+/// The area of a legend placed in `inner`, in logical pixels with y down.
+fn place(placement: LegendPlacement, inner: Rect, width: f64, height: f64) -> Rect {
+    let left = inner.x0 + MARGIN;
+    let right = inner.x1 - MARGIN - width;
+    let top = inner.y0 + MARGIN;
+    let bottom = inner.y1 - MARGIN - height;
+
+    let origin = match placement {
+        LegendPlacement::TopLeft => Point::new(left, top),
+        LegendPlacement::TopRight | LegendPlacement::Best => Point::new(right, top),
+        LegendPlacement::BottomLeft => Point::new(left, bottom),
+        LegendPlacement::BottomRight => Point::new(right, bottom),
+        LegendPlacement::OutsideRight => Point::new(inner.x1 + MARGIN, top),
+    };
+    Rect::from_origin_size(origin, (width, height))
+}
+
+fn faded(colour: [f32; 4], alpha: f32) -> Color {
+    let [r, g, b, a] = colour;
+    Color::new([r, g, b, a * alpha])
+}
+
+/// Draws a short horizontal piece of `line` centred on `centre`.
+fn draw_line(
+    scene: &mut Scene,
+    transform: Affine,
+    line: &Line<'_>,
+    centre: Point,
+    width: f64,
+    size: f64,
+    alpha: f32,
+) {
+    let thickness = (line.thickness as f64).min(size / 2.);
+    let cap = match line.cap {
+        LineCap::Round => kurbo::Cap::Round,
+        LineCap::Butt => kurbo::Cap::Butt,
+        LineCap::Square => kurbo::Cap::Square,
+    };
+    let dashes = line
+        .dash
+        .pattern(line.cap)
+        .into_iter()
+        .map(|length| length as f64 * thickness);
+    let stroke = Stroke::new(thickness)
+        .with_caps(cap)
+        .with_dashes(0., dashes);
+
+    // Leave room for round and square caps.
+    let half = width / 2. - thickness / 2.;
+    scene.stroke(
+        &stroke,
+        transform,
+        faded(line.colour, alpha),
+        None,
+        &Segment::new(centre - (half, 0.), centre + (half, 0.)),
+    );
+}
+
+/// Draws the marker of `scatter` centred on `centre`, no wider than `size`.
+fn draw_marker(
+    scene: &mut Scene,
+    transform: Affine,
+    scatter: &Scatter<'_>,
+    centre: Point,
+    size: f64,
+    alpha: f32,
+) {
+    // Markers are sized like the scatter shader's, which reach 1.4 times the
+    // radius.
+    let radius = (scatter.radius as f64).min(size / 2. / 1.4);
+    let polygon = |points: &[(f64, f64)]| {
+        let mut path = BezPath::new();
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let point = Point::new(x * radius, y * radius);
+            if i == 0 {
+                path.move_to(point);
+            } else {
+                path.line_to(point);
+            }
+        }
+        path.close_path();
+        path
+    };
+    let plus = || {
+        let (a, b) = (1.1, 0.2);
+        polygon(&[
+            (-b, -a),
+            (b, -a),
+            (b, -b),
+            (a, -b),
+            (a, b),
+            (b, b),
+            (b, a),
+            (-b, a),
+            (-b, b),
+            (-a, b),
+            (-a, -b),
+            (-b, -b),
+        ])
+    };
+    // An equilateral triangle with sides of 2.26 centred on its centroid.
+    let k = 1.13 / 3f64.sqrt();
+    let path = match scatter.marker {
+        Marker::Circle => Circle::new((0., 0.), radius).to_path(0.1),
+        Marker::Square => Rect::new(-0.8, -0.8, 0.8, 0.8)
+            .scale_from_origin(radius)
+            .to_path(0.1),
+        Marker::Diamond => polygon(&[(0., -1.13), (1.13, 0.), (0., 1.13), (-1.13, 0.)]),
+        Marker::TriangleUp => polygon(&[(0., -2. * k), (1.13, k), (-1.13, k)]),
+        Marker::TriangleDown => polygon(&[(0., 2. * k), (-1.13, -k), (1.13, -k)]),
+        Marker::Cross => Affine::rotate(PI / 4.) * plus(),
+        Marker::Plus => plus(),
+        Marker::Star => {
+            let points: Vec<_> = (0..10)
+                .map(|i| {
+                    let r = if i % 2 == 0 { 1.4 } else { 1.4 * 0.45 };
+                    let angle = -PI / 2. + i as f64 * PI / 5.;
+                    (r * angle.cos(), r * angle.sin())
+                })
+                .collect();
+            polygon(&points)
+        }
+    };
+
+    let transform = transform * Affine::translate(centre.to_vec2());
+    let colour = faded(scatter.colour, alpha);
+    match scatter.stroke {
+        Some(width) => scene.stroke(&Stroke::new(width as f64), transform, colour, None, &path),
+        None => scene.fill(Fill::NonZero, transform, colour, None, &path),
+    }
+}
@@ -0,0 +1,129 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    colour: vec4<f32>,
+    thickness: f32,
+    join: u32,
+    cap: u32,
+    miter_limit: f32,
+    length: u32,
+    dash_count: u32,
+    step: u32,
+    _pad_0: u32,
+    dashes: array<vec4<f32>, 2>,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+@group(1) @binding(2) var<storage, read_write> arc_lengths: array<f32>;
+
+const THREADS: u32 = 256u;
+
+var<workgroup> totals: array<f32, THREADS>;
+
+const STEP_NONE: u32 = 0u;
+const STEP_PRE: u32 = 1u;
+const STEP_MID: u32 = 2u;
+const STEP_POST: u32 = 3u;
+
+// Step lines are drawn through extra vertices between each pair of points,
+// which are generated here rather than stored.
+fn vertices_per_point() -> u32 {
+    switch params.step {
+        case STEP_PRE, STEP_POST: {
+            return 2u;
+        }
+        case STEP_MID: {
+            return 3u;
+        }
+        default: {
+            return 1u;
+        }
+    }
+}
+
+fn vertex_count() -> u32 {
+    if (params.length == 0u) {
+        return 0u;
+    }
+    return (params.length - 1u) * vertices_per_point() + 1u;
+}
+
+fn vertex(i: u32) -> vec2<f32> {
+    let per_point = vertices_per_point();
+    let index = i / per_point;
+    let offset = i % per_point;
+    if (offset == 0u) {
+        return points[index];
+    }
+
+    let a = points[index];
+    let b = points[index + 1u];
+    if (is_gap(a) || is_gap(b)) {
+        return vec2<f32>(bitcast<f32>(0x7fc00000u));
+    }
+    switch params.step {
+        case STEP_PRE: {
+            return vec2<f32>(a.x, b.y);
+        }
+        case STEP_POST: {
+            return vec2<f32>(b.x, a.y);
+        }
+        default: {
+            let mid = (a.x + b.x) / 2.0;
+            return vec2<f32>(mid, select(b.y, a.y, offset == 1u));
+        }
+    }
+}
+
+fn to_screen(point: vec2<f32>) -> vec2<f32> {
+    let ndc = (scene.projection_matrix * vec4<f32>(to_plane(point), 0.0, 1.0)).xy;
+    return ndc * 0.5 * scene.viewport_size;
+}
+
+// The length in pixels of the segment ending at vertex(i).
+fn segment_length(i: u32) -> f32 {
+    if (i == 0u) {
+        return 0.0;
+    }
+    let a = vertex(i - 1u);
+    let b = vertex(i);
+    if (is_gap(a) || is_gap(b)) {
+        return 0.0;
+    }
+    return distance(to_screen(a), to_screen(b));
+}
+
+// Computes the distance along the line to each vertex in a single workgroup.
+// Each thread sums a contiguous chunk of segments, and then offsets its chunk
+// by the totals of the chunks before it.
+@compute @workgroup_size(THREADS)
+fn cs_main(@builtin(local_invocation_index) index: u32) {
+    let count = vertex_count();
+    let chunk = (count + THREADS - 1u) / THREADS;
+    let start = min(index * chunk, count);
+    let end = min(start + chunk, count);
+
+    var total = 0.0;
+    for (var i = start; i < end; i++) {
+        total += segment_length(i);
+    }
+    totals[index] = total;
+
+    workgroupBarrier();
+
+    var offset = 0.0;
+    for (var i = 0u; i < index; i++) {
+        offset += totals[i];
+    }
+    for (var i = start; i < end; i++) {
+        offset += segment_length(i);
+        arc_lengths[i] = offset;
+    }
+}
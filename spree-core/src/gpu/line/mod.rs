@@ -1,54 +1,258 @@
 use bytemuck::{Pod, Zeroable};
 use vello::wgpu;
 
-use crate::layer::Line;
+use super::{LayerRenderer, SceneParams, create_compute_pipeline, to_buffer};
+use crate::layer::{Line, LineCap, LineJoin, MAX_DASHES, Step};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Pod, Zeroable)]
 pub(super) struct PerLineParams {
     colour: [f32; 4],
     thickness: f32,
-    _padding: [f32; 3],
+    join: u32,
+    cap: u32,
+    miter_limit: f32,
+    length: u32,
+    dash_count: u32,
+    step: u32,
+    _padding: u32,
+    dashes: [f32; MAX_DASHES],
+}
+
+/// The number of vertices the line is drawn through, including those added
+/// between points by [`Step`].
+fn vertex_count(line: &Line<'_>) -> u32 {
+    let per_point = match line.step {
+        None => 1,
+        Some(Step::Pre | Step::Post) => 2,
+        Some(Step::Mid) => 3,
+    };
+    (line.data.len() as u32).saturating_sub(1) * per_point + u32::from(!line.data.is_empty())
 }
 
 pub(super) struct Renderer {
-    is_miter: bool,
+    arc_length_pipeline: wgpu::ComputePipeline,
+    arc_length_group_0_layout: wgpu::BindGroupLayout,
+    arc_length_group_1_layout: wgpu::BindGroupLayout,
+    // Bound in place of the arc lengths for solid lines.
+    empty: wgpu::Buffer,
+}
+
+impl Renderer {
+    fn record_arc_lengths(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        scene_params: &SceneParams,
+        params: &wgpu::Buffer,
+        layer: &Line<'_>,
+    ) -> wgpu::Buffer {
+        let arc_lengths = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("line arc lengths"),
+            size: (vertex_count(layer).max(1) as usize * size_of::<f32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let bind_group0 = scene_params.create_bind_group(
+            device,
+            &self.arc_length_group_0_layout,
+            "line arc length",
+        );
+        let bind_group1 = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("line arc length bind group 1"),
+            layout: &self.arc_length_group_1_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: layer.data.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: arc_lengths.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("line arc length pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(&self.arc_length_pipeline);
+        compute_pass.set_bind_group(0, &bind_group0, &[]);
+        compute_pass.set_bind_group(1, &bind_group1, &[]);
+        compute_pass.dispatch_workgroups(1, 1, 1);
+
+        arc_lengths
+    }
 }
 
-impl super::LayerRenderer for Renderer {
+impl LayerRenderer for Renderer {
     type Layer<'a> = Line<'a>;
 
     type PerLayerParams = PerLineParams;
 
     const NAME: &'static str = "line";
 
-    fn new() -> Self {
-        Self { is_miter: false }
+    fn new(device: &wgpu::Device) -> Self {
+        let arc_length_group_0_layout = SceneParams::create_group_layout(device, "line arc length");
+        let arc_length_group_1_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("line arc length group 1 layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ..Self::_WITH_POINTS[0]
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ..Self::_WITH_POINTS[1]
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let arc_length_pipeline = create_compute_pipeline(
+            device,
+            "line arc length",
+            include_wgsl_with!(["gaps.wgsl", "polar.wgsl"], "arc_length.wgsl"),
+            &[&arc_length_group_0_layout, &arc_length_group_1_layout],
+        );
+
+        let empty = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("line empty arc lengths"),
+            size: size_of::<f32>() as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            arc_length_pipeline,
+            arc_length_group_0_layout,
+            arc_length_group_1_layout,
+            empty,
+        }
     }
 
     fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
-        #[allow(unreachable_code)]
-        if self.is_miter {
-            todo!("miter shader needs to be updated");
-            wgpu::include_wgsl!("miter.wgsl")
-        } else {
-            wgpu::include_wgsl!("round.wgsl")
-        }
+        include_wgsl_with!(["gaps.wgsl", "polar.wgsl"], "render.wgsl")
     }
 
     fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
-        if self.is_miter {
-            (0..(data.data.len() * 2) as u32, 0..1)
-        } else {
-            (0..6, 0..(data.data.len() as u32).saturating_sub(1))
-        }
+        // A single point is still drawn, as a dot, by one instance.
+        let instances = match vertex_count(data) {
+            1 => 1,
+            count => count.saturating_sub(1),
+        };
+        (0..4, 0..instances)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITH_POINTS[0],
+                Self::_WITH_POINTS[1],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
     }
 
     fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        let (join, miter_limit) = match data.join {
+            LineJoin::Round => (0, 0.),
+            LineJoin::Miter { limit } => (1, limit),
+            LineJoin::Bevel => (2, 0.),
+        };
+        let step = match data.step {
+            None => 0,
+            Some(Step::Pre) => 1,
+            Some(Step::Mid) => 2,
+            Some(Step::Post) => 3,
+        };
+        let cap = match data.cap {
+            LineCap::Round => 0,
+            LineCap::Butt => 1,
+            LineCap::Square => 2,
+        };
+
+        let pattern = data.dash.pattern(data.cap);
+        let mut dashes = [0.; MAX_DASHES];
+        for (dash, length) in dashes.iter_mut().zip(&pattern) {
+            *dash = length * data.thickness;
+        }
+        // A pattern without any length would never advance.
+        let dash_count = if dashes.iter().sum::<f32>() > 0. {
+            pattern.len() as u32
+        } else {
+            0
+        };
+
         PerLineParams {
             colour: data.colour,
             thickness: data.thickness,
-            _padding: [0., 0., 0.],
+            join,
+            cap,
+            miter_limit,
+            length: data.data.len() as u32,
+            dash_count,
+            step,
+            _padding: 0,
+            dashes,
         }
     }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        scene_params: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+
+        let arc_lengths = if params.dash_count > 0 && layer.data.len() > 1 {
+            &self.record_arc_lengths(device, encoder, scene_params, &params_buffer, layer)
+        } else {
+            &self.empty
+        };
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: layer.data.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: arc_lengths.as_entire_binding(),
+                },
+            ],
+        })
+    }
 }
@@ -0,0 +1,332 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+const JOIN_ROUND: u32 = 0u;
+const JOIN_MITER: u32 = 1u;
+const JOIN_BEVEL: u32 = 2u;
+
+const CAP_ROUND: u32 = 0u;
+const CAP_BUTT: u32 = 1u;
+const CAP_SQUARE: u32 = 2u;
+
+struct Params {
+    colour: vec4<f32>,
+    thickness: f32,
+    join: u32,
+    cap: u32,
+    miter_limit: f32,
+    length: u32,
+    dash_count: u32,
+    step: u32,
+    _pad_0: u32,
+    // Alternating on and off lengths in pixels.
+    dashes: array<vec4<f32>, 2>,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+// The distance in pixels along the line to each vertex. Only bound for dashed
+// lines.
+@group(1) @binding(2) var<storage, read> arc_lengths: array<f32>;
+
+const STEP_NONE: u32 = 0u;
+const STEP_PRE: u32 = 1u;
+const STEP_MID: u32 = 2u;
+const STEP_POST: u32 = 3u;
+
+// Step lines are drawn through extra vertices between each pair of points,
+// which are generated here rather than stored.
+fn vertices_per_point() -> u32 {
+    switch params.step {
+        case STEP_PRE, STEP_POST: {
+            return 2u;
+        }
+        case STEP_MID: {
+            return 3u;
+        }
+        default: {
+            return 1u;
+        }
+    }
+}
+
+fn vertex_count() -> u32 {
+    if (params.length == 0u) {
+        return 0u;
+    }
+    return (params.length - 1u) * vertices_per_point() + 1u;
+}
+
+fn vertex(i: u32) -> vec2<f32> {
+    let per_point = vertices_per_point();
+    let index = i / per_point;
+    let offset = i % per_point;
+    if (offset == 0u) {
+        return points[index];
+    }
+
+    let a = points[index];
+    let b = points[index + 1u];
+    if (is_gap(a) || is_gap(b)) {
+        return vec2<f32>(bitcast<f32>(0x7fc00000u));
+    }
+    switch params.step {
+        case STEP_PRE: {
+            return vec2<f32>(a.x, b.y);
+        }
+        case STEP_POST: {
+            return vec2<f32>(b.x, a.y);
+        }
+        default: {
+            let mid = (a.x + b.x) / 2.0;
+            return vec2<f32>(mid, select(b.y, a.y, offset == 1u));
+        }
+    }
+}
+
+// Converts a data point to logical pixels relative to the centre of the
+// viewport, so that lengths are the same in both directions.
+fn to_screen(point: vec2<f32>) -> vec2<f32> {
+    let ndc = (scene.projection_matrix * vec4<f32>(to_plane(point), 0.0, 1.0)).xy;
+    return ndc * 0.5 * scene.viewport_size;
+}
+
+// The direction from `start` to `end`, or zero if there is no such direction.
+fn direction(start: vec2<f32>, end: vec2<f32>) -> vec2<f32> {
+    let delta = end - start;
+    if (length(delta) < 0.0001) {
+        return vec2<f32>(0.0);
+    }
+    return normalize(delta);
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) frag_pos: vec2<f32>,
+    @location(1) @interpolate(flat) p0: vec2<f32>,
+    @location(2) @interpolate(flat) p1: vec2<f32>,
+    // The direction from p0 to the previous point, or zero if p0 is capped.
+    @location(3) @interpolate(flat) before: vec2<f32>,
+    // The direction from p1 to the next point, or zero if p1 is capped.
+    @location(4) @interpolate(flat) after: vec2<f32>,
+    // The distance along the line to p0.
+    @location(5) @interpolate(flat) arc_start: f32,
+};
+
+// Each instance draws the segment between vertex(i) and vertex(i + 1) as a
+// quad aligned to the segment. The fragment shader then clips the quad to the
+// bisectors of the neighbouring segments so that joins tile without overlap.
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    let last = vertex_count() - 1u;
+    let a = vertex(instance_index);
+    // A line of one point has a single instance, with nothing after it.
+    var b = vec2<f32>(bitcast<f32>(0x7fc00000u));
+    if (instance_index < last) {
+        b = vertex(instance_index + 1u);
+    }
+
+    var p0: vec2<f32>;
+    var p1: vec2<f32>;
+    var before = vec2<f32>(0.0);
+    var after = vec2<f32>(0.0);
+    if (is_gap(a) || is_gap(b)) {
+        // Segments touching a gap collapse to a degenerate quad so the line is
+        // split in two, with each side getting a cap. A point with gaps on both
+        // sides is drawn by one of its segments as a zero length segment, which
+        // is only its caps, so butt caps leave it out as in SVG.
+        let lone_a = !is_gap(a) && (instance_index == 0u || is_gap(vertex(instance_index - 1u)));
+        let lone_b = !is_gap(b) && instance_index + 1u == last;
+        if (!lone_a && !lone_b) {
+            out.position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+            return out;
+        }
+        p0 = to_screen(select(b, a, lone_a));
+        p1 = p0;
+    } else {
+        p0 = to_screen(a);
+        p1 = to_screen(b);
+
+        if (instance_index > 0u && !is_gap(vertex(instance_index - 1u))) {
+            before = direction(p0, to_screen(vertex(instance_index - 1u)));
+        }
+        if (instance_index + 1u < last && !is_gap(vertex(instance_index + 2u))) {
+            after = direction(p1, to_screen(vertex(instance_index + 2u)));
+        }
+    }
+
+    var dir = direction(p0, p1);
+    if (all(dir == vec2<f32>(0.0))) {
+        dir = vec2<f32>(1.0, 0.0);
+    }
+    let normal = vec2<f32>(-dir.y, dir.x);
+
+    // Leave a pixel for anti-aliasing, and room for miters along the segment.
+    let half_width = params.thickness / 2.0 + 1.0;
+    var extension = half_width;
+    if (params.join == JOIN_MITER) {
+        extension *= max(params.miter_limit, 1.0);
+    }
+
+    var corners = array<vec2<f32>, 4>(
+        p0 - dir * extension - normal * half_width,
+        p0 - dir * extension + normal * half_width,
+        p1 + dir * extension - normal * half_width,
+        p1 + dir * extension + normal * half_width,
+    );
+    let corner = corners[vertex_index];
+
+    out.frag_pos = corner;
+    out.p0 = p0;
+    out.p1 = p1;
+    out.before = before;
+    out.after = after;
+    if (params.dash_count > 0u) {
+        out.arc_start = arc_lengths[instance_index];
+    }
+    out.position = vec4<f32>(corner / (0.5 * scene.viewport_size), 0.0, 1.0);
+    return out;
+}
+
+// The signed distance, in pixels, to the end of the segment at `p` heading in
+// direction `dir`, where `next` is the direction of the neighbouring segment.
+//
+// Returns -half_width where the end places no constraint on the segment so it
+// can be combined with the distance to the sides of the segment using `max`.
+fn end_distance(q: vec2<f32>, p: vec2<f32>, dir: vec2<f32>, next: vec2<f32>, half_width: f32) -> f32 {
+    let along = dot(q - p, dir);
+
+    if (all(next == vec2<f32>(0.0))) {
+        switch params.cap {
+            case CAP_BUTT: {
+                return along;
+            }
+            case CAP_SQUARE: {
+                return along - half_width;
+            }
+            default: {
+                if (along > 0.0) {
+                    return length(q - p) - half_width;
+                }
+                return -half_width;
+            }
+        }
+    }
+
+    // The line doubles back on itself, so there is no sensible join.
+    let bisector = dir + next;
+    if (dot(bisector, bisector) < 0.0001) {
+        return along;
+    }
+    // Everything past the bisector belongs to the neighbouring segment.
+    if (dot(q - p, normalize(bisector)) > 0.0) {
+        discard;
+    }
+    if (along <= 0.0) {
+        return -half_width;
+    }
+
+    let normal = vec2<f32>(-dir.y, dir.x);
+    let next_normal = vec2<f32>(-next.y, next.x);
+    let turn = dir.x * next.y - dir.y * next.x;
+    let outward = -sign(turn) * normalize(normal + next_normal);
+    // The inside of the join is already covered by the two segments.
+    if (dot(q - p, outward) <= 0.0) {
+        return -half_width;
+    }
+
+    let cos_half_angle = abs(dot(outward, normal));
+    let bevel = dot(q - p, outward) - half_width * cos_half_angle;
+    switch params.join {
+        case JOIN_MITER: {
+            // The ratio of the miter length to the thickness.
+            if (1.0 / cos_half_angle <= params.miter_limit) {
+                return -half_width;
+            }
+            return bevel;
+        }
+        case JOIN_BEVEL: {
+            return bevel;
+        }
+        default: {
+            return length(q - p) - half_width;
+        }
+    }
+}
+
+fn dash(i: u32) -> f32 {
+    return params.dashes[i / 4u][i % 4u];
+}
+
+// The signed distance, in pixels along the line, to the nearest dash.
+fn dash_distance(arc_length: f32) -> f32 {
+    var period = 0.0;
+    for (var i = 0u; i < params.dash_count; i++) {
+        period += dash(i);
+    }
+    let phase = arc_length - floor(arc_length / period) * period;
+
+    var dist = period;
+    var start = 0.0;
+    for (var i = 0u; i < params.dash_count; i += 2u) {
+        let end = start + dash(i);
+        // Also check the dash's neighbours in the previous and next periods.
+        for (var k = -1; k <= 1; k++) {
+            let shift = f32(k) * period;
+            dist = min(dist, max(start + shift - phase, phase - end - shift));
+        }
+        start = end + dash(i + 1u);
+    }
+    return dist;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let half_width = params.thickness / 2.0;
+
+    var dir = direction(in.p0, in.p1);
+    if (all(dir == vec2<f32>(0.0))) {
+        dir = vec2<f32>(1.0, 0.0);
+    }
+    let normal = vec2<f32>(-dir.y, dir.x);
+
+    let side = abs(dot(in.frag_pos - in.p0, normal)) - half_width;
+    let start = end_distance(in.frag_pos, in.p0, -dir, in.before, half_width);
+    let end = end_distance(in.frag_pos, in.p1, dir, in.after, half_width);
+    var dist = max(side, max(start, end));
+
+    if (params.dash_count > 0u) {
+        let along = dash_distance(in.arc_start + dot(in.frag_pos - in.p0, dir));
+        // Each dash is capped like the ends of the line.
+        switch params.cap {
+            case CAP_BUTT: {
+                dist = max(dist, along);
+            }
+            case CAP_SQUARE: {
+                dist = max(dist, along - half_width);
+            }
+            default: {
+                let across = abs(dot(in.frag_pos - in.p0, normal));
+                dist = max(dist, length(vec2<f32>(max(along, 0.0), across)) - half_width);
+            }
+        }
+    }
+
+    let alpha = 1.0 - smoothstep(-1.0, 0.0, dist);
+
+    if (alpha < 0.01) {
+        discard;
+    }
+
+    return vec4<f32>(params.colour.rgb, params.colour.a * alpha);
+}
@@ -1,15 +1,55 @@
+/// Like `wgpu::include_wgsl!`, but with shared snippets from `src/gpu`
+/// prepended, such as `gaps.wgsl` for `is_nan` and `is_gap`.
+macro_rules! include_wgsl_with {
+    ([$($snippet:literal),+ $(,)?], $path:literal) => {
+        vello::wgpu::ShaderModuleDescriptor {
+            label: Some($path),
+            source: vello::wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(concat!(
+                $(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/gpu/", $snippet)),)+
+                include_str!($path),
+            ))),
+        }
+    };
+}
+
+mod annotation;
+mod area;
+mod bar;
+mod box_plot;
 mod buffer;
+mod candle;
+mod categorical_axis;
+mod contour;
+mod contour_label;
+mod density;
+mod error_bar;
 mod grid;
+mod heatmap;
+mod histogram;
+mod histogram_2d;
+mod legend;
 mod line;
+mod quiver;
+mod readback;
+mod reference;
 mod renderer;
 mod scatter;
 mod scene_params;
+mod shape;
+mod stem;
+mod text;
+mod ticks;
+mod tooltip;
+mod violin;
+mod waterfall;
 
 use std::{fmt::Debug, mem::size_of, ops::Range};
 
 use bytemuck::{Pod, Zeroable};
 use vello::wgpu::{self, ShaderModuleDescriptor, util::DeviceExt};
 
+use crate::layer::Colormap;
+
 pub(crate) use self::{buffer::GpuBuffer, renderer::Renderer, scene_params::SceneParams};
 
 struct Wrapper<R>
@@ -36,7 +76,7 @@ where
             immediate_size: 0,
         });
 
-        let inner = R::new();
+        let inner = R::new(device);
 
         Self {
             render_pipeline: inner.create_render_pipeline(device, &pipeline_layout),
@@ -60,7 +100,9 @@ trait LayerRenderer: Sized {
 
     const NAME: &'static str;
 
-    fn new() -> Self;
+    const TOPOLOGY: wgpu::PrimitiveTopology = wgpu::PrimitiveTopology::TriangleStrip;
+
+    fn new(device: &wgpu::Device) -> Self;
 
     fn shader(&self) -> ShaderModuleDescriptor<'static>;
 
@@ -102,7 +144,7 @@ trait LayerRenderer: Sized {
             },
             multiview_mask: None,
             primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                topology: Self::TOPOLOGY,
                 strip_index_format: None,
                 front_face: wgpu::FrontFace::Ccw,
                 cull_mode: None,
@@ -166,12 +208,20 @@ trait LayerRenderer: Sized {
 
     fn create_per_layer_params(&self, layer: &Self::Layer<'_>) -> Self::PerLayerParams;
 
+    /// Creates the bind group for a layer.
+    ///
+    /// This is called before the layer's render pass begins, so any compute
+    /// passes the layer depends on can be recorded into `encoder`.
     fn create_per_layer_group<'a>(
         &self,
         device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        scene_params: &SceneParams,
         layout: &wgpu::BindGroupLayout,
         layer: &Self::Layer<'a>,
     ) -> wgpu::BindGroup {
+        let _ = (encoder, scene_params);
+
         let name = format!("{} bind group 1", Self::NAME);
         let params = self.create_per_layer_params(layer);
         let params_buffer = to_buffer(device, &name, &params);
@@ -216,3 +266,70 @@ where
         usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
     })
 }
+
+/// Uploads the stops of a colormap for a shader to read as an
+/// `array<vec4<f32>>`.
+fn colormap_buffer(device: &wgpu::Device, name: &str, colormap: &Colormap<'_>) -> wgpu::Buffer {
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&format!("{name} colormap")),
+        contents: bytemuck::cast_slice(&colormap.stops()),
+        usage: wgpu::BufferUsages::STORAGE,
+    })
+}
+
+fn create_compute_pipeline(
+    device: &wgpu::Device,
+    name: &str,
+    shader: ShaderModuleDescriptor<'_>,
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+) -> wgpu::ComputePipeline {
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some(&format!("{name} pipeline layout")),
+        bind_group_layouts,
+        immediate_size: 0,
+    });
+    let shader = device.create_shader_module(shader);
+
+    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some(&format!("{name} pipeline")),
+        layout: Some(&pipeline_layout),
+        module: &shader,
+        entry_point: Some("cs_main"),
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+        cache: None,
+    })
+}
+
+/// The number of workgroups needed to run one invocation per item.
+///
+/// Workgroups are laid out in a 2D grid as each dimension is limited to 65535
+/// workgroups, so shaders should compute their index as `id.x + id.y *
+/// num_workgroups.x * workgroup_size`.
+fn workgroups(items: usize, workgroup_size: u32) -> (u32, u32) {
+    const MAX_WORKGROUPS: u32 = 65535;
+
+    let workgroups = (items as u32).div_ceil(workgroup_size).max(1);
+    let x = workgroups.min(MAX_WORKGROUPS);
+    (x, workgroups.div_ceil(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workgroups_cover_every_item() {
+        assert_eq!(workgroups(0, 64), (1, 1));
+        assert_eq!(workgroups(64, 64), (1, 1));
+        assert_eq!(workgroups(65, 64), (2, 1));
+    }
+
+    #[test]
+    fn large_dispatches_wrap_into_rows() {
+        assert_eq!(workgroups(65535 * 64, 64), (65535, 1));
+        assert_eq!(workgroups(65535 * 64 + 1, 64), (65535, 2));
+        let (x, y) = workgroups(10_000_000, 64);
+        assert!(x <= 65535);
+        assert!((x * y * 64) as usize >= 10_000_000);
+    }
+}
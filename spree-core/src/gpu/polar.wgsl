@@ -0,0 +1,24 @@
+// Prepended to shaders by `include_wgsl_with!`. Expects the shader to bind
+// `scene`.
+
+const PI: f32 = 3.14159265;
+const TAU: f32 = 6.28318531;
+
+// Maps a data point onto the plane the projection matrix applies to. Polar
+// plots take points as (theta, r) with theta in radians.
+fn to_plane(point: vec2<f32>) -> vec2<f32> {
+    if (scene.polar == 0.0) {
+        return point;
+    }
+    let angle = scene.polar * point.x + scene.rotation;
+    return point.y * vec2<f32>(cos(angle), sin(angle));
+}
+
+// The inverse of `to_plane`, with theta in [0, 2π).
+fn from_plane(point: vec2<f32>) -> vec2<f32> {
+    if (scene.polar == 0.0) {
+        return point;
+    }
+    let angle = scene.polar * (atan2(point.y, point.x) - scene.rotation);
+    return vec2<f32>(angle - floor(angle / TAU) * TAU, length(point));
+}
@@ -0,0 +1,115 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{LayerRenderer, SceneParams, colormap_buffer, to_buffer};
+use crate::layer::{ArrowScale, Colormap, Quiver};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerQuiverParams {
+    scale: f32,
+    in_pixels: u32,
+    thickness: f32,
+    range_min: f32,
+    range_max: f32,
+    _padding: [f32; 3],
+}
+
+pub(super) struct Renderer {}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Quiver<'a>;
+    type PerLayerParams = PerQuiverParams;
+
+    const NAME: &'static str = "quiver";
+
+    const TOPOLOGY: wgpu::PrimitiveTopology = wgpu::PrimitiveTopology::TriangleList;
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl", "polar.wgsl", "colormap.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        // The shaft is two triangles and the head is one.
+        let len = data.data.len().min(data.vectors.len());
+        (0..9, 0..len as u32)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITH_POINTS[0],
+                Self::_WITH_POINTS[1],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        let (scale, in_pixels) = match data.scale {
+            ArrowScale::Data(scale) => (scale, 0),
+            ArrowScale::Pixels(scale) => (scale, 1),
+        };
+
+        PerQuiverParams {
+            scale,
+            in_pixels,
+            thickness: data.thickness,
+            range_min: data.range.min as f32,
+            range_max: data.range.max as f32,
+            _padding: [0.; 3],
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+        // A colormap with one stop gives every arrow the same colour.
+        let colour = [layer.colour];
+        let colormap = layer.colormap.unwrap_or(Colormap::Custom(&colour));
+        let colormap = colormap_buffer(device, &name, &colormap);
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: layer.data.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: layer.vectors.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: colormap.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
@@ -0,0 +1,93 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    scale: f32,
+    // Whether `scale` is in pixels per unit rather than data units.
+    in_pixels: u32,
+    thickness: f32,
+    range_min: f32,
+    range_max: f32,
+    _pad_0: f32,
+    _pad_1: f32,
+    _pad_2: f32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+@group(1) @binding(2) var<storage, read> vectors: array<vec2<f32>>;
+@group(1) @binding(3) var<storage, read> colormap: array<vec4<f32>>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) colour: vec4<f32>,
+};
+
+fn to_screen(point: vec2<f32>) -> vec2<f32> {
+    let ndc = (scene.projection_matrix * vec4<f32>(to_plane(point), 0.0, 1.0)).xy;
+    return ndc * 0.5 * scene.viewport_size;
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+
+    let point = points[instance_index];
+    let vector = vectors[instance_index];
+    if (is_gap(point) || is_gap(vector)) {
+        return out;
+    }
+
+    let tail = to_screen(point);
+    var tip = to_screen(point + vector * params.scale);
+    if (params.in_pixels == 1u) {
+        tip = tail + vector * params.scale;
+    }
+
+    let delta = tip - tail;
+    let len = length(delta);
+    if (len < 1e-3) {
+        return out;
+    }
+    let dir = delta / len;
+    let normal = vec2<f32>(-dir.y, dir.x);
+
+    // Heads shrink with arrows too short to fit them.
+    let head_length = min(params.thickness * 3.0 + 3.0, len);
+    let head_base = tip - dir * head_length;
+    let shaft = normal * params.thickness / 2.0;
+    let head = normal * head_length / 2.0;
+
+    var corners = array<vec2<f32>, 9>(
+        tail - shaft,
+        head_base - shaft,
+        tail + shaft,
+        tail + shaft,
+        head_base - shaft,
+        head_base + shaft,
+        tip,
+        head_base + head,
+        head_base - head,
+    );
+
+    out.position = vec4<f32>(corners[vertex_index] / (0.5 * scene.viewport_size), 0.0, 1.0);
+    // An empty range maps every vector to the start of the colormap.
+    let span = params.range_max - params.range_min;
+    let t = select(0.0, (length(vector) - params.range_min) / span, span != 0.0);
+    out.colour = sample_colormap(t);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.colour;
+}
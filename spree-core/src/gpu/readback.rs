@@ -0,0 +1,97 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU8, Ordering},
+};
+
+use vello::wgpu;
+
+const PENDING: u8 = 0;
+const MAPPED: u8 = 1;
+const FAILED: u8 = 2;
+
+enum State {
+    Idle,
+    /// A copy has been recorded but not submitted yet.
+    Recorded,
+    /// Waiting for the buffer to be mapped, or for mapping to fail.
+    Mapping(Arc<AtomicU8>),
+}
+
+/// A buffer that is copied into on the GPU and read on the CPU once it is
+/// mapped, usually a frame or more later.
+pub(super) struct Readback {
+    buffer: wgpu::Buffer,
+    state: State,
+}
+
+impl Readback {
+    pub(super) fn new(device: &wgpu::Device, label: &str, size: u64) -> Self {
+        Self {
+            buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            state: State::Idle,
+        }
+    }
+
+    /// Whether a copy can be recorded, which it can't while the buffer is
+    /// being mapped.
+    pub(super) fn is_free(&self) -> bool {
+        !matches!(self.state, State::Mapping(_))
+    }
+
+    /// Records copying `size` bytes of `source` from `offset` into the start
+    /// of the buffer. A later copy in the same frame replaces an earlier one.
+    pub(super) fn record(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        source: &wgpu::Buffer,
+        offset: u64,
+        size: u64,
+    ) {
+        debug_assert!(self.is_free(), "readback buffer is being mapped");
+        encoder.copy_buffer_to_buffer(source, offset, &self.buffer, 0, size);
+        self.state = State::Recorded;
+    }
+
+    /// Starts mapping the buffer if a copy was recorded. This must be called
+    /// after the frame's commands are submitted.
+    pub(super) fn after_submit(&mut self) {
+        if let State::Recorded = self.state {
+            let status = Arc::new(AtomicU8::new(PENDING));
+            let mapped = status.clone();
+            self.buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    let status = if result.is_ok() { MAPPED } else { FAILED };
+                    mapped.store(status, Ordering::Release);
+                });
+            self.state = State::Mapping(status);
+        }
+    }
+
+    /// Reads the buffer with `read` once it is mapped, then frees it for the
+    /// next copy. If mapping failed, the buffer is freed without being read.
+    pub(super) fn read<T>(&mut self, read: impl FnOnce(&[u8]) -> T) -> Option<T> {
+        let State::Mapping(status) = &self.state else {
+            return None;
+        };
+
+        match status.load(Ordering::Acquire) {
+            MAPPED => {
+                let value = read(&self.buffer.slice(..).get_mapped_range());
+                self.buffer.unmap();
+                self.state = State::Idle;
+                Some(value)
+            }
+            FAILED => {
+                self.state = State::Idle;
+                None
+            }
+            _ => None,
+        }
+    }
+}
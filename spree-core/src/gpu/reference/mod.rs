@@ -0,0 +1,48 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use crate::layer::{Axis, Reference};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerReferenceParams {
+    colour: [f32; 4],
+    range_min: f32,
+    range_max: f32,
+    thickness: f32,
+    axis: u32,
+}
+
+pub(super) struct Renderer {}
+
+impl super::LayerRenderer for Renderer {
+    type Layer<'a> = Reference;
+    type PerLayerParams = PerReferenceParams;
+
+    const NAME: &'static str = "reference";
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["polar.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, _: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..4, 0..1)
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerReferenceParams {
+            colour: data.colour,
+            range_min: data.range.min as f32,
+            range_max: data.range.max as f32,
+            thickness: data.thickness,
+            axis: match data.axis {
+                Axis::X => 0,
+                Axis::Y => 1,
+            },
+        }
+    }
+}
@@ -0,0 +1,74 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    colour: vec4<f32>,
+    // The range covered along `axis`, which is a single value for lines.
+    range_min: f32,
+    range_max: f32,
+    thickness: f32,
+    axis: u32,
+}
+@group(1) @binding(0) var<uniform> params: Params;
+
+const AXIS_X: u32 = 0u;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // Interpolated on the plane as the polar mapping is not linear.
+    @location(0) plane_pos: vec2<f32>,
+};
+
+// Covers the whole viewport.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let ndc = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u)) * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc, 0.0, 1.0);
+    let unprojected = scene.inverse_projection_matrix * vec4<f32>(ndc, 0.0, 1.0);
+    out.plane_pos = unprojected.xy / unprojected.w;
+    return out;
+}
+
+// The distance outside of the range in pixels, negative inside it.
+fn distance_to_range(plane_pos: vec2<f32>) -> f32 {
+    if (scene.polar == 0.0) {
+        let pixel_size = fwidth(plane_pos);
+        let on_x = params.axis == AXIS_X;
+        let value = select(plane_pos.y, plane_pos.x, on_x);
+        let pixel = select(pixel_size.y, pixel_size.x, on_x);
+        return max(params.range_min - value, value - params.range_max) / pixel;
+    }
+
+    // The plane has the same scale on both axes.
+    let pixel = fwidth(plane_pos.x);
+    let polar = from_plane(plane_pos);
+    if (params.axis != AXIS_X) {
+        return max(params.range_min - polar.y, polar.y - params.range_max) / pixel;
+    }
+
+    // Angles wrap, so measure from the centre of the range.
+    let centre = (params.range_min + params.range_max) / 2.0;
+    let half_range = (params.range_max - params.range_min) / 2.0;
+    let delta = polar.x - centre + PI;
+    let angle = abs(delta - floor(delta / TAU) * TAU - PI) - half_range;
+    let arc = select(sign(angle) * polar.y, polar.y * sin(angle), abs(angle) < PI / 2.0);
+    return arc / pixel;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let dist = distance_to_range(in.plane_pos);
+    let alpha = clamp(params.thickness / 2.0 + 0.5 - dist, 0.0, 1.0);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(params.colour.rgb, params.colour.a * alpha);
+}
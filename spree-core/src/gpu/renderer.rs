@@ -1,14 +1,51 @@
-use vello::wgpu::{
-    self, CommandEncoder, Device, Queue, Surface, SurfaceConfiguration, TextureFormat, TextureView,
+use std::cell::RefCell;
+
+use vello::{
+    AaConfig, AaSupport, RenderParams, RendererOptions, Scene,
+    kurbo::Point,
+    peniko::Color,
+    wgpu::{
+        self, CommandEncoder, Device, Queue, Surface, SurfaceConfiguration, TextureFormat,
+        TextureView,
+    },
 };
 
-use super::{LayerRenderer, SceneParams, Wrapper};
-use crate::layout::PlotInstanceLayout;
+use super::{LayerRenderer, SceneParams, Wrapper, legend::Series};
+use crate::{
+    layer::{Baseline, Line, Scatter, Side},
+    layout::PlotInstanceLayout,
+};
 
 pub struct Renderer<'a> {
+    annotation: Wrapper<super::annotation::Renderer>,
+    area: Wrapper<super::area::Renderer>,
+    bar: Wrapper<super::bar::Renderer>,
+    box_plot: Wrapper<super::box_plot::Renderer>,
+    candle: Wrapper<super::candle::Renderer>,
+    contour_fill: Wrapper<super::contour::FillRenderer>,
+    contour_line: Wrapper<super::contour::LineRenderer>,
+    density: Wrapper<super::density::Renderer>,
+    error_bar: Wrapper<super::error_bar::Renderer>,
+    heatmap: Wrapper<super::heatmap::Renderer>,
+    histogram: Wrapper<super::histogram::Renderer>,
+    histogram_2d: Wrapper<super::histogram_2d::Renderer>,
     line: Wrapper<super::line::Renderer>,
+    quiver: Wrapper<super::quiver::Renderer>,
+    reference: Wrapper<super::reference::Renderer>,
     scatter: Wrapper<super::scatter::Renderer>,
+    stem: Wrapper<super::stem::Renderer>,
+    violin: Wrapper<super::violin::Renderer>,
+    waterfall: Wrapper<super::waterfall::Renderer>,
     grid: Wrapper<super::grid::Renderer>,
+    // Created on first use, as compiling vello's shaders is slow.
+    vello: RefCell<Option<vello::Renderer>>,
+    // The vello scene waiting to be composited over the framebuffer, built up
+    // from consecutive vello layers so that they share one vello submit.
+    overlay: RefCell<Option<Scene>>,
+    overlay_view: TextureView,
+    legend: RefCell<super::legend::State>,
+    // The mouse position in physical pixels, if it is over the window.
+    cursor: Option<Point>,
     surface: Surface<'a>,
     device: Device,
     msaa_view: TextureView,
@@ -49,6 +86,22 @@ impl<'a> Renderer<'a> {
         R: LayerRenderer,
         I: Iterator<Item = R::Layer<'b>>,
     {
+        let bind_group0 =
+            scene_params.create_bind_group(&self.device, &stuff.group_0_layout, "line");
+        let layers: Vec<_> = datas
+            .map(|data| {
+                let counts = stuff.inner.counts(&data);
+                let bind_group1 = stuff.inner.create_per_layer_group(
+                    &self.device,
+                    encoder,
+                    &scene_params,
+                    &stuff.group_1_layout,
+                    &data,
+                );
+                (counts, bind_group1)
+            })
+            .collect();
+
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some(&format!("{} render pass", R::NAME)),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -69,26 +122,17 @@ impl<'a> Renderer<'a> {
             multiview_mask: None,
         });
         render_pass.set_pipeline(&stuff.render_pipeline);
-
-        let bind_group0 =
-            scene_params.create_bind_group(&self.device, &stuff.group_0_layout, "line");
         render_pass.set_bind_group(0, &bind_group0, &[]);
 
-        for data in datas {
-            let (vertices, instances) = stuff.inner.counts(&data);
-
-            let bind_group1 =
-                stuff
-                    .inner
-                    .create_per_layer_group(&self.device, &stuff.group_1_layout, &data);
+        for ((vertices, instances), bind_group1) in layers {
             render_pass.set_bind_group(1, &bind_group1, &[]);
-
             render_pass.draw(vertices, instances);
         }
     }
 
     pub(crate) fn new(device: Device, queue: Queue, surface: Surface<'a>) -> Self {
         let msaa_texture = create_msaa_texture(&device, 1600, 1200);
+        let overlay_view = super::annotation::create_texture(&device, 1600, 1200);
 
         // let surface_caps = surface.get_capabilities(&adapter);
         // let surface_format = surface_caps
@@ -99,9 +143,31 @@ impl<'a> Renderer<'a> {
         //     .unwrap_or(surface_caps.formats[0]);
 
         Self {
+            annotation: Wrapper::new(&device),
+            area: Wrapper::new(&device),
+            bar: Wrapper::new(&device),
+            box_plot: Wrapper::new(&device),
+            candle: Wrapper::new(&device),
+            contour_fill: Wrapper::new(&device),
+            contour_line: Wrapper::new(&device),
+            density: Wrapper::new(&device),
+            error_bar: Wrapper::new(&device),
+            heatmap: Wrapper::new(&device),
+            histogram: Wrapper::new(&device),
+            histogram_2d: Wrapper::new(&device),
             line: Wrapper::new(&device),
+            quiver: Wrapper::new(&device),
+            reference: Wrapper::new(&device),
             scatter: Wrapper::new(&device),
+            stem: Wrapper::new(&device),
+            violin: Wrapper::new(&device),
+            waterfall: Wrapper::new(&device),
             grid: Wrapper::new(&device),
+            vello: RefCell::new(None),
+            overlay: RefCell::new(None),
+            overlay_view,
+            legend: RefCell::new(super::legend::State::new(&device)),
+            cursor: None,
             device,
             msaa_view: msaa_texture,
             surface,
@@ -126,12 +192,31 @@ impl<'a> Renderer<'a> {
 
             self.surface.configure(&self.device, &self.config);
             self.msaa_view = create_msaa_texture(&self.device, width, height);
+            self.overlay_view = super::annotation::create_texture(&self.device, width, height);
 
             // TODO: reconfigure line renderer?
             // self.is_surface_configured = true;
         }
     }
 
+    /// Hides or shows the series whose legend entry is at `position`, in
+    /// physical pixels. Returns whether anything changed.
+    pub(crate) fn toggle_legend_entry(&mut self, position: Point) -> bool {
+        self.legend.get_mut().toggle(position)
+    }
+
+    /// Moves the mouse to `position`, in physical pixels, or off the window.
+    /// Returns whether a redraw is needed to update tooltips.
+    pub(crate) fn hover(&mut self, position: Option<Point>) -> bool {
+        self.cursor = position;
+        self.histogram_2d.inner.has_tooltips()
+    }
+
+    /// The number of physical pixels per logical pixel.
+    fn scale(&self, scene_params: &SceneParams) -> f64 {
+        self.config.width as f64 / scene_params.viewport_size[0] as f64
+    }
+
     pub(crate) fn device(&self) -> &Device {
         &self.device
     }
@@ -140,7 +225,9 @@ impl<'a> Renderer<'a> {
         &self.queue
     }
 
-    pub(crate) fn render<'b, I>(&self, layers: I, layout: &PlotInstanceLayout)
+    /// Draws `layers`, returning whether another frame is needed to show data
+    /// read back from the GPU.
+    pub(crate) fn render<'b, I>(&self, layers: I, layout: &PlotInstanceLayout) -> bool
     where
         I: Iterator<Item = crate::Layer<'b>>,
     {
@@ -155,22 +242,54 @@ impl<'a> Renderer<'a> {
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
-        let scene_params = layout.scene_params();
         let background = wgpu::Color {
             r: 1.,
             g: 1.,
             b: 1.,
             a: 1.,
         };
-        layers.enumerate().for_each(|(i, layer)| {
-            let clear = if i == 0 { Some(background) } else { None };
-            self.render_layer(layer, &mut encoder, &view, clear, scene_params)
-        });
+        let mut layers: Vec<_> = layers.collect();
+        // Layers drawn on grids of cells only make sense on Cartesian axes.
+        if layout.scene_params().is_polar() {
+            layers.retain(|layer| {
+                !matches!(
+                    layer,
+                    crate::Layer::Heatmaps(_)
+                        | crate::Layer::Waterfalls(_)
+                        | crate::Layer::Contours(_)
+                        | crate::Layer::Histograms2d(_)
+                )
+            });
+        }
+        let series = Series::new(&layers, &self.legend.borrow());
+        self.legend.borrow().hide(&mut layers);
+        self.legend.borrow_mut().clear_hit_boxes();
+        let scale = self.scale(&layout.scene_params());
+        let cursor = self
+            .cursor
+            .map(|position| Point::new(position.x / scale, position.y / scale));
+        self.histogram_2d.inner.start_frame(cursor);
+
+        let mut clear = Some(background);
+        for layer in layers {
+            let layer_clear = if is_vello_only(&layer) {
+                None
+            } else {
+                // Overlays are composited before anything drawn over them.
+                self.draw_overlay(&mut encoder, &view, layout.scene_params(), &mut clear);
+                clear.take()
+            };
+            self.render_layer(layer, &mut encoder, &view, layer_clear, layout, &series);
+        }
+        self.draw_overlay(&mut encoder, &view, layout.scene_params(), &mut clear);
 
         self.queue.submit([encoder.finish()]);
+        self.legend.borrow_mut().after_submit();
+        self.histogram_2d.inner.after_submit();
         output.present();
 
         self.device.poll(wgpu::PollType::Poll).unwrap();
+        self.histogram_2d.inner.needs_redraw()
     }
 
     fn render_layer(
@@ -179,28 +298,318 @@ impl<'a> Renderer<'a> {
         encoder: &mut CommandEncoder,
         view: &TextureView,
         clear: Option<wgpu::Color>,
-        scene_params: SceneParams,
+        layout: &PlotInstanceLayout,
+        series: &Series,
     ) {
+        let scene_params = layout.scene_params();
+        let scale = self.scale(&scene_params);
         match layer {
             crate::Layer::Title(_) => todo!(),
             crate::Layer::XAxis { .. } => todo!(),
             crate::Layer::YAxis { .. } => todo!(),
-            crate::Layer::Lines(lines) => self.usee(
-                &self.line,
+            crate::Layer::Lines(lines) => {
+                // Each y axis has its own projection, so needs its own pass.
+                let (right, left): (Vec<_>, Vec<_>) = lines
+                    .into_iter()
+                    .partition(|line| line.y_axis == Side::Right);
+                self.usee(
+                    &self.line,
+                    encoder,
+                    view,
+                    scene_params,
+                    clear,
+                    left.into_iter(),
+                );
+                if !right.is_empty() {
+                    self.usee(
+                        &self.line,
+                        encoder,
+                        view,
+                        layout.scene_params_for(Side::Right),
+                        None,
+                        right.into_iter(),
+                    );
+                }
+            }
+            crate::Layer::Scatters(scatters) => {
+                let (right, left): (Vec<_>, Vec<_>) = scatters
+                    .into_iter()
+                    .partition(|scatter| scatter.y_axis == Side::Right);
+                self.usee(
+                    &self.scatter,
+                    encoder,
+                    view,
+                    scene_params,
+                    clear,
+                    left.into_iter(),
+                );
+                if !right.is_empty() {
+                    self.usee(
+                        &self.scatter,
+                        encoder,
+                        view,
+                        layout.scene_params_for(Side::Right),
+                        None,
+                        right.into_iter(),
+                    );
+                }
+            }
+            crate::Layer::Areas(areas) => {
+                let outlines: Vec<_> = areas
+                    .iter()
+                    .filter_map(|area| Some((area, area.outline?)))
+                    .flat_map(|(area, outline)| {
+                        let baseline = match area.baseline {
+                            Baseline::Constant(_) => None,
+                            Baseline::Buffer(baseline) => Some(baseline),
+                        };
+                        std::iter::once(area.data)
+                            .chain(baseline)
+                            .map(move |data| Line {
+                                thickness: outline.thickness,
+                                colour: outline.colour,
+                                ..Line::new(data)
+                            })
+                    })
+                    .collect();
+
+                self.usee(
+                    &self.area,
+                    encoder,
+                    view,
+                    scene_params,
+                    clear,
+                    areas.into_iter(),
+                );
+                if !outlines.is_empty() {
+                    self.usee(
+                        &self.line,
+                        encoder,
+                        view,
+                        scene_params,
+                        None,
+                        outlines.into_iter(),
+                    );
+                }
+            }
+            crate::Layer::Stems(stems) => {
+                let markers: Vec<_> = stems
+                    .iter()
+                    .filter_map(|stems| {
+                        Some(Scatter {
+                            radius: stems.radius,
+                            colour: stems.colour,
+                            marker: stems.marker?,
+                            ..Scatter::new(stems.data)
+                        })
+                    })
+                    .collect();
+
+                self.usee(
+                    &self.stem,
+                    encoder,
+                    view,
+                    scene_params,
+                    clear,
+                    stems.into_iter(),
+                );
+                if !markers.is_empty() {
+                    self.usee(
+                        &self.scatter,
+                        encoder,
+                        view,
+                        scene_params,
+                        None,
+                        markers.into_iter(),
+                    );
+                }
+            }
+            crate::Layer::Bars(bars) => self.usee(
+                &self.bar,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                bars.into_iter(),
+            ),
+            crate::Layer::Candles(candles) => self.usee(
+                &self.candle,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                candles.into_iter(),
+            ),
+            crate::Layer::BoxPlots(box_plots) => self.usee(
+                &self.box_plot,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                box_plots.into_iter(),
+            ),
+            crate::Layer::Violins(violins) => self.usee(
+                &self.violin,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                violins.into_iter(),
+            ),
+            crate::Layer::Quivers(quivers) => self.usee(
+                &self.quiver,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                quivers.into_iter(),
+            ),
+            crate::Layer::Histograms(histograms) => self.usee(
+                &self.histogram,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                histograms.into_iter(),
+            ),
+            crate::Layer::ErrorBars(error_bars) => self.usee(
+                &self.error_bar,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                error_bars.into_iter(),
+            ),
+            crate::Layer::Heatmaps(heatmaps) => self.usee(
+                &self.heatmap,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                heatmaps.into_iter(),
+            ),
+            crate::Layer::Waterfalls(waterfalls) => self.usee(
+                &self.waterfall,
                 encoder,
                 view,
                 scene_params,
                 clear,
-                lines.into_iter(),
+                waterfalls.into_iter(),
             ),
-            crate::Layer::Scatters(scatters) => self.usee(
-                &self.scatter,
+            crate::Layer::Densities(densities) => self.usee(
+                &self.density,
                 encoder,
                 view,
                 scene_params,
                 clear,
-                scatters.into_iter(),
+                densities.into_iter(),
             ),
+            crate::Layer::Histograms2d(histograms) => {
+                let tooltip = histograms.iter().rev().find_map(|h| h.tooltip);
+                self.usee(
+                    &self.histogram_2d,
+                    encoder,
+                    view,
+                    scene_params,
+                    clear,
+                    histograms.into_iter(),
+                );
+
+                if let Some(tooltip) = tooltip
+                    && let Some(count) = self.histogram_2d.inner.tooltip_count()
+                    && count > 0
+                    && let Some(position) = self.cursor
+                {
+                    let cursor = Point::new(position.x / scale, position.y / scale);
+                    let scene = super::tooltip::build_scene(
+                        &tooltip,
+                        &count.to_string(),
+                        cursor,
+                        scene_params.viewport_size,
+                        scale,
+                    );
+                    self.add_overlay(scene);
+                }
+            }
+            crate::Layer::Contours(contours) => {
+                let lines: Vec<_> = contours
+                    .iter()
+                    .filter(|contour| contour.lines.is_some())
+                    .copied()
+                    .collect();
+                let fills = contours
+                    .iter()
+                    .filter(|contour| contour.fill.is_some())
+                    .copied();
+
+                self.usee(
+                    &self.contour_fill,
+                    encoder,
+                    view,
+                    scene_params,
+                    clear,
+                    fills,
+                );
+                if !lines.is_empty() {
+                    self.usee(
+                        &self.contour_line,
+                        encoder,
+                        view,
+                        scene_params,
+                        None,
+                        lines.into_iter(),
+                    );
+                }
+                if contours.iter().any(|contour| contour.labels.is_some()) {
+                    let scene = super::contour_label::build_scene(
+                        &contours,
+                        &scene_params,
+                        &layout.padding,
+                        scale,
+                    );
+                    self.add_overlay(scene);
+                }
+            }
+            crate::Layer::References(references) => self.usee(
+                &self.reference,
+                encoder,
+                view,
+                scene_params,
+                clear,
+                references.into_iter(),
+            ),
+            crate::Layer::Annotations(annotations) => {
+                let scene = super::annotation::build_scene(&annotations, &scene_params, scale);
+                self.add_overlay(scene);
+            }
+            crate::Layer::Shapes(shapes) => {
+                let scene = super::shape::build_scene(&shapes, &scene_params, scale);
+                self.add_overlay(scene);
+            }
+            crate::Layer::CategoricalAxes(axes) => {
+                let scene = super::categorical_axis::build_scene(
+                    &axes,
+                    &scene_params,
+                    &layout.padding,
+                    scale,
+                );
+                self.add_overlay(scene);
+            }
+            crate::Layer::Ticks(ticks) => {
+                let scene = super::ticks::build_scene(&ticks, layout, scale);
+                self.add_overlay(scene);
+            }
+            crate::Layer::Legend(legend) => {
+                let scene = self.legend.borrow_mut().build_scene(
+                    &self.device,
+                    encoder,
+                    &legend,
+                    series,
+                    layout,
+                    scale,
+                );
+                self.add_overlay(scene);
+            }
             crate::Layer::Grid(grid) => self.usee(
                 &self.grid,
                 encoder,
@@ -211,4 +620,93 @@ impl<'a> Renderer<'a> {
             ),
         };
     }
+
+    /// Queues `scene` to be drawn over everything drawn so far.
+    fn add_overlay(&self, scene: Scene) {
+        match &mut *self.overlay.borrow_mut() {
+            Some(overlay) => overlay.append(&scene, None),
+            overlay => *overlay = Some(scene),
+        }
+    }
+
+    /// Draws the queued vello scene, if any, over the framebuffer. A vello
+    /// failure only loses the overlay.
+    fn draw_overlay(
+        &self,
+        encoder: &mut CommandEncoder,
+        view: &TextureView,
+        scene_params: SceneParams,
+        clear: &mut Option<wgpu::Color>,
+    ) {
+        let Some(scene) = self.overlay.take() else {
+            return;
+        };
+        let (width, height) = (self.config.width, self.config.height);
+
+        // vello submits its own work, so anything recorded so far that reads
+        // the overlay texture must be submitted before vello draws into it.
+        let recorded = std::mem::replace(
+            encoder,
+            self.device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Line Render Encoder"),
+                }),
+        );
+        self.queue.submit([recorded.finish()]);
+
+        let mut vello = self.vello.borrow_mut();
+        if vello.is_none() {
+            *vello = vello::Renderer::new(
+                &self.device,
+                RendererOptions {
+                    use_cpu: false,
+                    antialiasing_support: AaSupport::area_only(),
+                    num_init_threads: None,
+                    pipeline_cache: None,
+                },
+            )
+            .ok();
+        }
+        let drawn = vello.as_mut().is_some_and(|vello| {
+            vello
+                .render_to_texture(
+                    &self.device,
+                    &self.queue,
+                    &scene,
+                    &self.overlay_view,
+                    &RenderParams {
+                        base_color: Color::TRANSPARENT,
+                        width,
+                        height,
+                        antialiasing_method: AaConfig::Area,
+                    },
+                )
+                .is_ok()
+        });
+
+        // The pass still runs without an overlay in case it has to clear.
+        let overlay = drawn.then_some(super::annotation::Overlay {
+            view: &self.overlay_view,
+        });
+        self.usee(
+            &self.annotation,
+            encoder,
+            view,
+            scene_params,
+            clear.take(),
+            overlay.into_iter(),
+        );
+    }
+}
+
+/// Whether `layer` is drawn entirely by vello.
+fn is_vello_only(layer: &crate::Layer) -> bool {
+    matches!(
+        layer,
+        crate::Layer::Annotations(_)
+            | crate::Layer::Shapes(_)
+            | crate::Layer::CategoricalAxes(_)
+            | crate::Layer::Ticks(_)
+            | crate::Layer::Legend(_)
+    )
 }
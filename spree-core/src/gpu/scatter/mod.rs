@@ -1,17 +1,27 @@
 use bytemuck::{Pod, Zeroable};
 use vello::wgpu;
 
-use crate::layer::Scatter;
+use super::{SceneParams, colormap_buffer, to_buffer};
+use crate::layer::{Marker, PointColours, Scatter};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Pod, Zeroable)]
 pub(super) struct PerScatterParams {
     colour: [f32; 4],
     radius: f32,
-    _padding: [f32; 3],
+    colour_mode: u32,
+    has_radii: u32,
+    range_min: f32,
+    range_max: f32,
+    marker: u32,
+    stroke: f32,
+    _padding: f32,
 }
 
-pub(super) struct Renderer {}
+pub(super) struct Renderer {
+    // Bound in place of any per-point attributes the layer doesn't have.
+    empty: wgpu::Buffer,
+}
 
 impl super::LayerRenderer for Renderer {
     type Layer<'a> = Scatter<'a>;
@@ -19,23 +29,133 @@ impl super::LayerRenderer for Renderer {
 
     const NAME: &'static str = "scatter";
 
-    fn new() -> Self {
-        Self {}
+    fn new(device: &wgpu::Device) -> Self {
+        let empty = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("scatter empty attributes"),
+            size: size_of::<[f32; 4]>() as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        Self { empty }
     }
 
     fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
-        wgpu::include_wgsl!("render.wgsl")
+        include_wgsl_with!(["gaps.wgsl", "polar.wgsl", "colormap.wgsl"], "render.wgsl")
     }
 
     fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
-        (0..6, 0..data.data.len() as u32)
+        let colours = match data.colours {
+            None => usize::MAX,
+            Some(PointColours::Mapped { values, .. }) => values.len(),
+            Some(PointColours::Rgba(colours)) => colours.len(),
+        };
+        let radii = data.radii.map_or(usize::MAX, |radii| radii.len());
+
+        (0..6, 0..data.data.len().min(colours).min(radii) as u32)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        let attribute = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            ..Self::_WITH_POINTS[0]
+        };
+
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITH_POINTS[0],
+                Self::_WITH_POINTS[1],
+                attribute(2),
+                attribute(3),
+                attribute(4),
+            ],
+        })
     }
 
     fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        let (colour_mode, range) = match data.colours {
+            None => (0, None),
+            Some(PointColours::Mapped { range, .. }) => (1, Some(range)),
+            Some(PointColours::Rgba(_)) => (2, None),
+        };
+
         PerScatterParams {
             colour: data.colour,
             radius: data.radius,
-            _padding: [0., 0., 0.],
+            colour_mode,
+            has_radii: u32::from(data.radii.is_some()),
+            range_min: range.map_or(0., |range| range.min as f32),
+            range_max: range.map_or(1., |range| range.max as f32),
+            marker: match data.marker {
+                Marker::Circle => 0,
+                Marker::Square => 1,
+                Marker::Diamond => 2,
+                Marker::TriangleUp => 3,
+                Marker::TriangleDown => 4,
+                Marker::Cross => 5,
+                Marker::Plus => 6,
+                Marker::Star => 7,
+            },
+            stroke: data.stroke.unwrap_or(0.),
+            _padding: 0.,
         }
     }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+
+        let empty = self.empty.as_entire_binding();
+        let (colours, colormap) = match layer.colours {
+            None => (empty.clone(), None),
+            Some(PointColours::Mapped {
+                values, colormap, ..
+            }) => (
+                values.as_entire_binding(),
+                Some(colormap_buffer(device, &name, &colormap)),
+            ),
+            Some(PointColours::Rgba(colours)) => (colours.as_entire_binding(), None),
+        };
+        let radii = layer
+            .radii
+            .map_or(empty.clone(), |radii| radii.as_entire_binding());
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: layer.data.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: colours,
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: colormap
+                        .as_ref()
+                        .map_or(empty, |colormap| colormap.as_entire_binding()),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: radii,
+                },
+            ],
+        })
+    }
 }
@@ -2,23 +2,154 @@ struct SceneParams {
     projection_matrix: mat4x4<f32>,
     inverse_projection_matrix: mat4x4<f32>,
     viewport_size: vec2<f32>,
-    _padding: vec2<f32>,
+    polar: f32,
+    rotation: f32,
 };
 @group(0) @binding(0) var<uniform> scene: SceneParams;
 
 struct Params {
     colour: vec4<f32>,
     radius: f32,
+    colour_mode: u32,
+    has_radii: u32,
+    range_min: f32,
+    range_max: f32,
+    marker: u32,
+    // The width of the outline of hollow markers, or zero for filled markers.
+    stroke: f32,
     _pad_0: f32,
-    _pad_1: f32,
-    _pad_2: f32,
 }
 @group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
 @group(1) @binding(1) var<uniform> scatter: Params;
+// Either a value to map through the colormap or four colour channels for each
+// point, depending on `colour_mode`.
+@group(1) @binding(2) var<storage, read> colours: array<f32>;
+@group(1) @binding(3) var<storage, read> colormap: array<vec4<f32>>;
+@group(1) @binding(4) var<storage, read> radii: array<f32>;
+
+const COLOUR_UNIFORM: u32 = 0u;
+const COLOUR_MAPPED: u32 = 1u;
+const COLOUR_RGBA: u32 = 2u;
+
+const MARKER_CIRCLE: u32 = 0u;
+const MARKER_SQUARE: u32 = 1u;
+const MARKER_DIAMOND: u32 = 2u;
+const MARKER_TRIANGLE_UP: u32 = 3u;
+const MARKER_TRIANGLE_DOWN: u32 = 4u;
+const MARKER_CROSS: u32 = 5u;
+const MARKER_PLUS: u32 = 6u;
+const MARKER_STAR: u32 = 7u;
+
+// How far the largest marker extends past its radius.
+const MARKER_EXTENT: f32 = 1.4;
+
+fn point_colour(i: u32) -> vec4<f32> {
+    switch scatter.colour_mode {
+        case COLOUR_MAPPED: {
+            // An empty range maps every value to the start of the colormap.
+            let span = scatter.range_max - scatter.range_min;
+            return sample_colormap(select(0.0, (colours[i] - scatter.range_min) / span, span != 0.0));
+        }
+        case COLOUR_RGBA: {
+            return vec4<f32>(colours[i * 4u], colours[i * 4u + 1u], colours[i * 4u + 2u], colours[i * 4u + 3u]);
+        }
+        default: {
+            return scatter.colour;
+        }
+    }
+}
+
+// Signed distance functions for each marker, negative inside. These are
+// adapted from https://iquilezles.org/articles/distfunctions2d/.
+
+fn sd_box(p: vec2<f32>, half_size: vec2<f32>) -> f32 {
+    let d = abs(p) - half_size;
+    return length(max(d, vec2<f32>(0.0))) + min(max(d.x, d.y), 0.0);
+}
+
+fn sd_rhombus(p: vec2<f32>, b: vec2<f32>) -> f32 {
+    let q = abs(p);
+    let c = b - 2.0 * q;
+    let h = clamp((c.x * b.x - c.y * b.y) / dot(b, b), -1.0, 1.0);
+    let d = length(q - 0.5 * b * vec2<f32>(1.0 - h, 1.0 + h));
+    return d * sign(q.x * b.y + q.y * b.x - b.x * b.y);
+}
+
+// An upwards equilateral triangle centred on its centroid, with sides of
+// length `2 * r`.
+fn sd_triangle(p: vec2<f32>, r: f32) -> f32 {
+    let k = sqrt(3.0);
+    var q = vec2<f32>(abs(p.x) - r, p.y + r / k);
+    if (q.x + k * q.y > 0.0) {
+        q = vec2<f32>(q.x - k * q.y, -k * q.x - q.y) / 2.0;
+    }
+    q.x -= clamp(q.x, -2.0 * r, 0.0);
+    return -length(q) * sign(q.y);
+}
+
+// A plus with arms `b.x` long and `2 * b.y` thick.
+fn sd_plus(p: vec2<f32>, b: vec2<f32>) -> f32 {
+    var q = abs(p);
+    if (q.y > q.x) {
+        q = q.yx;
+    }
+    let d = q - b;
+    let k = max(d.y, d.x);
+    let w = select(vec2<f32>(b.y - q.x, -k), d, k > 0.0);
+    return sign(k) * length(max(w, vec2<f32>(0.0)));
+}
+
+// An upwards five pointed star whose inner vertices are `inner` times as far
+// from the centre as its points.
+fn sd_star(p: vec2<f32>, r: f32, inner: f32) -> f32 {
+    let k1 = vec2<f32>(0.809016994375, -0.587785252292);
+    let k2 = vec2<f32>(-k1.x, k1.y);
+    var q = vec2<f32>(abs(p.x), p.y);
+    q -= 2.0 * max(dot(k1, q), 0.0) * k1;
+    q -= 2.0 * max(dot(k2, q), 0.0) * k2;
+    q = vec2<f32>(abs(q.x), q.y - r);
+    let ba = inner * vec2<f32>(-k1.y, k1.x) - vec2<f32>(0.0, 1.0);
+    let h = clamp(dot(q, ba) / dot(ba, ba), 0.0, r);
+    return length(q - ba * h) * sign(q.y * ba.x - q.x * ba.y);
+}
+
+// Markers are sized to look about as large as a circle of the same radius.
+fn marker_distance(p: vec2<f32>, radius: f32) -> f32 {
+    switch scatter.marker {
+        case MARKER_SQUARE: {
+            return sd_box(p, vec2<f32>(0.8 * radius));
+        }
+        case MARKER_DIAMOND: {
+            return sd_rhombus(p, vec2<f32>(1.13 * radius));
+        }
+        case MARKER_TRIANGLE_UP: {
+            return sd_triangle(p, 1.13 * radius);
+        }
+        case MARKER_TRIANGLE_DOWN: {
+            return sd_triangle(-p, 1.13 * radius);
+        }
+        case MARKER_CROSS: {
+            let rotated = vec2<f32>(p.x + p.y, p.y - p.x) * 0.70710678;
+            return sd_plus(rotated, vec2<f32>(1.1, 0.2) * radius);
+        }
+        case MARKER_PLUS: {
+            return sd_plus(p, vec2<f32>(1.1, 0.2) * radius);
+        }
+        case MARKER_STAR: {
+            return sd_star(p, MARKER_EXTENT * radius, 0.45);
+        }
+        default: {
+            return length(p) - radius;
+        }
+    }
+}
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
-    @location(0) local_pos: vec2<f32>, // Passes the -1 to 1 quad coordinates to the fragment shader
+    // The position relative to the point in pixels.
+    @location(0) local_pos: vec2<f32>,
+    @location(1) @interpolate(flat) colour: vec4<f32>,
+    @location(2) @interpolate(flat) radius: f32,
 };
 
 @vertex
@@ -28,7 +159,12 @@ fn vs_main(
 ) -> VertexOutput {
     var out: VertexOutput;
 
-    let ndc_center = scene.projection_matrix * vec4<f32>(points[instance_index], 0.0, 1.0);
+    if (is_gap(points[instance_index])) {
+        out.position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        return out;
+    }
+
+    let ndc_center = scene.projection_matrix * vec4<f32>(to_plane(points[instance_index]), 0.0, 1.0);
 
     var quad_pos = array<vec2<f32>, 6>(
         vec2<f32>(-1.0, -1.0),
@@ -39,12 +175,16 @@ fn vs_main(
         vec2<f32>( 1.0,  1.0) 
     );
     
-    let local_coord = quad_pos[vertex_index];
-    out.local_pos = local_coord;
+    let radius = select(scatter.radius, radii[instance_index], scatter.has_radii == 1u);
+    // Leave a pixel around the marker for antialiasing.
+    let half_size = radius * MARKER_EXTENT + 1.0;
 
-    let ndc_radius = (scatter.radius / scene.viewport_size) * 2.0;
+    let local_coord = quad_pos[vertex_index] * half_size;
+    out.local_pos = local_coord;
+    out.colour = point_colour(instance_index);
+    out.radius = radius;
 
-    let offset = local_coord * ndc_radius;
+    let offset = local_coord / (0.5 * scene.viewport_size);
     out.position = ndc_center + vec4<f32>(offset, 0.0, 0.0);
 
     return out;
@@ -52,14 +192,16 @@ fn vs_main(
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let dist = length(in.local_pos);
+    var dist = marker_distance(in.local_pos, in.radius);
+    if (scatter.stroke > 0.0) {
+        dist = abs(dist + 0.5 * scatter.stroke) - 0.5 * scatter.stroke;
+    }
+
+    let alpha = clamp(0.5 - dist, 0.0, 1.0);
 
-    let pixel_size = fwidth(dist);
-    let alpha = 1.0 - smoothstep(1.0 - pixel_size, 1.0, dist);
-    
     if (alpha < 0.01) {
         discard;
     }
 
-    return vec4<f32>(scatter.colour.rgb, scatter.colour.a * alpha);
+    return vec4<f32>(in.colour.rgb, in.colour.a * alpha);
 }
\ No newline at end of file
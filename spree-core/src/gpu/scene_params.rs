@@ -1,5 +1,8 @@
 use bytemuck::{Pod, Zeroable};
-use vello::wgpu;
+use vello::{
+    kurbo::{Affine, Point},
+    wgpu,
+};
 
 use super::to_buffer;
 
@@ -9,10 +12,45 @@ pub(crate) struct SceneParams {
     pub(crate) projection_matrix: [[f32; 4]; 4],
     pub(crate) inverse_projection_matrix: [[f32; 4]; 4],
     pub(crate) viewport_size: [f32; 2],
-    pub(crate) _padding: [f32; 2],
+    /// The direction of increasing theta in polar plots, either `1.` for
+    /// anticlockwise or `-1.` for clockwise, and `0.` in Cartesian plots.
+    pub(crate) polar: f32,
+    /// The angle of theta = 0 in polar plots, anticlockwise from the right.
+    pub(crate) rotation: f32,
 }
 
 impl SceneParams {
+    pub(crate) fn is_polar(&self) -> bool {
+        self.polar != 0.
+    }
+
+    /// Maps a data point onto the plane that the projection applies to, as
+    /// `to_plane` does in the shaders.
+    pub(crate) fn map_to_plane(&self, point: Point) -> Point {
+        if !self.is_polar() {
+            return point;
+        }
+        let angle = f64::from(self.polar) * point.x + f64::from(self.rotation);
+        Point::new(point.y * angle.cos(), point.y * angle.sin())
+    }
+
+    /// The transform from the plane to logical pixels with y down, as used by
+    /// vello. In Cartesian plots this is the transform from data coordinates.
+    pub(crate) fn pixel_transform(&self) -> Affine {
+        // The projection is orthographic, so it is affine and w is one.
+        let m = self.projection_matrix.map(|column| column.map(f64::from));
+        let [width, height] = self.viewport_size.map(|size| f64::from(size) / 2.);
+
+        Affine::new([
+            m[0][0] * width,
+            -m[0][1] * height,
+            m[1][0] * width,
+            -m[1][1] * height,
+            (m[3][0] + 1.) * width,
+            (1. - m[3][1]) * height,
+        ])
+    }
+
     pub(crate) fn create_group_layout(
         device: &wgpu::Device,
         layer_name: &str,
@@ -21,7 +59,7 @@ impl SceneParams {
             label: Some(&format!("{layer_name} group 0 layout")),
             entries: &[wgpu::BindGroupLayoutEntry {
                 binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT | wgpu::ShaderStages::COMPUTE,
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
@@ -53,3 +91,73 @@ impl SceneParams {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+
+    /// Shows data from (0, 0) to (10, 5) in a viewport of 200 by 100 pixels.
+    fn cartesian() -> SceneParams {
+        SceneParams {
+            projection_matrix: [
+                [0.2, 0., 0., 0.],
+                [0., 0.4, 0., 0.],
+                [0., 0., 1., 0.],
+                [-1., -1., 0., 1.],
+            ],
+            inverse_projection_matrix: [[0.; 4]; 4],
+            viewport_size: [200., 100.],
+            polar: 0.,
+            rotation: 0.,
+        }
+    }
+
+    /// Checks points are equal to within the precision of the `f32` matrices.
+    fn assert_near(actual: Point, expected: (f64, f64)) {
+        let expected = Point::from(expected);
+        assert!(
+            actual.distance(expected) < 1e-4,
+            "{actual:?} is not {expected:?}"
+        );
+    }
+
+    #[test]
+    fn pixel_transform_puts_y_down() {
+        let to_pixels = cartesian().pixel_transform();
+        assert_near(to_pixels * Point::new(0., 0.), (0., 100.));
+        assert_near(to_pixels * Point::new(10., 5.), (200., 0.));
+        assert_near(to_pixels * Point::new(5., 2.5), (100., 50.));
+    }
+
+    #[test]
+    fn cartesian_points_stay_put_on_the_plane() {
+        assert_eq!(
+            cartesian().map_to_plane(Point::new(3., -2.)),
+            Point::new(3., -2.)
+        );
+    }
+
+    #[test]
+    fn polar_points_turn_with_the_rotation() {
+        let anticlockwise = SceneParams {
+            polar: 1.,
+            ..cartesian()
+        };
+        assert_near(anticlockwise.map_to_plane(Point::new(0., 2.)), (2., 0.));
+        assert_near(
+            anticlockwise.map_to_plane(Point::new(FRAC_PI_2, 1.)),
+            (0., 1.),
+        );
+
+        // A compass, with theta = 0 at the top and increasing clockwise.
+        let compass = SceneParams {
+            polar: -1.,
+            rotation: FRAC_PI_2 as f32,
+            ..cartesian()
+        };
+        assert_near(compass.map_to_plane(Point::new(0., 1.)), (0., 1.));
+        assert_near(compass.map_to_plane(Point::new(FRAC_PI_2, 1.)), (1., 0.));
+    }
+}
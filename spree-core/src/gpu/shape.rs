@@ -0,0 +1,115 @@
+use vello::{
+    Scene,
+    kurbo::{Affine, BezPath, Circle, Ellipse, PathEl, Point, Rect, Shape as _, Stroke, flatten},
+    peniko::{Color, Fill},
+};
+
+use super::SceneParams;
+use crate::layer::{Geometry, Shape};
+
+/// How closely curves are approximated in logical pixels.
+const TOLERANCE: f64 = 0.1;
+
+/// The longest edge in logical pixels that polar plots draw as a straight line.
+const SEGMENT_LENGTH: f64 = 2.;
+
+/// Builds a vello scene drawing `shapes` at the current view.
+///
+/// `scale` is the number of physical pixels per logical pixel.
+pub(super) fn build_scene(shapes: &[Shape<'_>], scene_params: &SceneParams, scale: f64) -> Scene {
+    let mut scene = Scene::new();
+    let transform = Affine::scale(scale);
+
+    for shape in shapes {
+        // Paths are moved into pixels before stroking, so outlines keep their
+        // thickness however the axes are scaled.
+        let path = match shape.geometry {
+            Geometry::Polygon(vertices) => {
+                let mut path = BezPath::new();
+                for (i, &[x, y]) in vertices.iter().enumerate() {
+                    if i == 0 {
+                        path.move_to((x, y));
+                    } else {
+                        path.line_to((x, y));
+                    }
+                }
+                path.close_path();
+                path_to_pixels(&path, scene_params)
+            }
+            Geometry::Rectangle(bounds) => {
+                let rect = Rect::new(bounds.x.min, bounds.y.min, bounds.x.max, bounds.y.max);
+                path_to_pixels(&rect.to_path(TOLERANCE), scene_params)
+            }
+            Geometry::Circle {
+                centre: [x, y],
+                radius,
+            } if !scene_params.is_polar() => (scene_params.pixel_transform()
+                * Ellipse::from(Circle::new((x, y), radius)))
+            .to_path(TOLERANCE),
+            Geometry::Circle {
+                centre: [x, y],
+                radius,
+            } => path_to_pixels(
+                &Circle::new((x, y), radius).to_path(TOLERANCE),
+                scene_params,
+            ),
+        };
+
+        if let Some(fill) = shape.fill {
+            scene.fill(Fill::NonZero, transform, Color::new(fill), None, &path);
+        }
+        if let Some(outline) = shape.outline {
+            let stroke = Stroke::new(outline.thickness as f64);
+            scene.stroke(&stroke, transform, Color::new(outline.colour), None, &path);
+        }
+    }
+
+    scene
+}
+
+/// Moves a path in data coordinates into logical pixels.
+///
+/// Straight edges in polar plots become curves, so they are split into short
+/// segments before being mapped.
+fn path_to_pixels(path: &BezPath, scene_params: &SceneParams) -> BezPath {
+    let to_pixels = scene_params.pixel_transform();
+    if !scene_params.is_polar() {
+        return to_pixels * path.clone();
+    }
+
+    let [a, b, ..] = to_pixels.as_coeffs();
+    let pixels_per_unit = a.hypot(b);
+    let map = |point: Point| to_pixels * scene_params.map_to_plane(point);
+
+    let mut pixels = BezPath::new();
+    let mut subpath_start = Point::ZERO;
+    let mut last = Point::ZERO;
+    let line_to = |pixels: &mut BezPath, from: Point, to: Point| {
+        let arc = (to.x - from.x).abs() * from.y.abs().max(to.y.abs());
+        let length = (arc + (to.y - from.y).abs()) * pixels_per_unit;
+        let segments = (length / SEGMENT_LENGTH).ceil().clamp(1., 1024.) as usize;
+        for i in 1..=segments {
+            pixels.line_to(map(from.lerp(to, i as f64 / segments as f64)));
+        }
+    };
+
+    flatten(path, TOLERANCE / pixels_per_unit, |element| match element {
+        PathEl::MoveTo(point) => {
+            pixels.move_to(map(point));
+            subpath_start = point;
+            last = point;
+        }
+        PathEl::LineTo(point) => {
+            line_to(&mut pixels, last, point);
+            last = point;
+        }
+        PathEl::ClosePath => {
+            line_to(&mut pixels, last, subpath_start);
+            pixels.close_path();
+            last = subpath_start;
+        }
+        // Flattening only produces lines.
+        _ => {}
+    });
+    pixels
+}
@@ -0,0 +1,111 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{LayerRenderer, SceneParams, to_buffer};
+use crate::layer::{Baseline, Orientation, Stems};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerStemsParams {
+    colour: [f32; 4],
+    thickness: f32,
+    baseline: f32,
+    has_baseline_points: u32,
+    orientation: u32,
+}
+
+pub(super) struct Renderer {}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Stems<'a>;
+    type PerLayerParams = PerStemsParams;
+
+    const NAME: &'static str = "stem";
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl", "polar.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        let len = match data.baseline {
+            Baseline::Constant(_) => data.data.len(),
+            Baseline::Buffer(baseline) => data.data.len().min(baseline.len()),
+        };
+        (0..4, 0..len as u32)
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITH_POINTS[0],
+                Self::_WITH_POINTS[1],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        let (baseline, has_baseline_points) = match data.baseline {
+            Baseline::Constant(baseline) => (baseline, 0),
+            Baseline::Buffer(_) => (0., 1),
+        };
+
+        PerStemsParams {
+            colour: data.colour,
+            thickness: data.thickness,
+            baseline,
+            has_baseline_points,
+            orientation: match data.orientation {
+                Orientation::Vertical => 0,
+                Orientation::Horizontal => 1,
+            },
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+
+        // The shader ignores the baseline points for a constant baseline, but
+        // something still has to be bound.
+        let baseline = match layer.baseline {
+            Baseline::Constant(_) => layer.data,
+            Baseline::Buffer(baseline) => baseline,
+        };
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: layer.data.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: baseline.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
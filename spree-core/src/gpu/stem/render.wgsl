@@ -0,0 +1,62 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+const ORIENTATION_HORIZONTAL: u32 = 1u;
+
+struct Params {
+    colour: vec4<f32>,
+    thickness: f32,
+    baseline: f32,
+    // Whether stems start at `baseline_points` instead of `baseline`.
+    has_baseline_points: u32,
+    orientation: u32,
+}
+@group(1) @binding(0) var<storage, read> points: array<vec2<f32>>;
+@group(1) @binding(1) var<uniform> params: Params;
+@group(1) @binding(2) var<storage, read> baseline_points: array<vec2<f32>>;
+
+fn to_screen(point: vec2<f32>) -> vec2<f32> {
+    let ndc = (scene.projection_matrix * vec4<f32>(to_plane(point), 0.0, 1.0)).xy;
+    return ndc * 0.5 * scene.viewport_size;
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32
+) -> @builtin(position) vec4<f32> {
+    let point = points[instance_index];
+    var base = vec2<f32>(params.baseline);
+    if (params.has_baseline_points == 1u) {
+        base = baseline_points[instance_index];
+    }
+
+    if (is_gap(point) || is_gap(base)) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    // Stems run along the value axis from the baseline to the point.
+    var start = vec2<f32>(point.x, base.y);
+    var across = vec2<f32>(params.thickness / 2.0, 0.0);
+    if (params.orientation == ORIENTATION_HORIZONTAL) {
+        start = vec2<f32>(base.x, point.y);
+        across = across.yx;
+    }
+
+    let ends = array<vec2<f32>, 2>(to_screen(start), to_screen(point));
+    let side = f32(vertex_index & 1u) * 2.0 - 1.0;
+    let screen = ends[vertex_index >> 1u] + across * side;
+
+    return vec4<f32>(screen / (0.5 * scene.viewport_size), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return params.colour;
+}
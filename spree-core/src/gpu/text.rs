@@ -0,0 +1,91 @@
+use vello::{
+    Glyph, Scene,
+    kurbo::Affine,
+    peniko::{Color, Fill},
+    skrifa::{
+        GlyphId, MetadataProvider,
+        instance::{LocationRef, Size},
+    },
+};
+
+use crate::layer::{Font, HorizontalAlign};
+
+/// Text laid out in lines, measured in pixels.
+pub(super) struct TextLayout {
+    /// The glyphs of each line, starting at zero, and the line's width.
+    lines: Vec<(Vec<Glyph>, f64)>,
+    line_height: f64,
+    ascent: f64,
+    pub(super) width: f64,
+    pub(super) height: f64,
+}
+
+impl TextLayout {
+    /// Lays out each line of `text` left to right, without shaping or kerning.
+    pub(super) fn new(text: &str, font: &Font, size: f32) -> Self {
+        let font = font.font_ref();
+        let size = Size::new(size);
+        let metrics = font.metrics(size, LocationRef::default());
+        let glyph_metrics = font.glyph_metrics(size, LocationRef::default());
+        let charmap = font.charmap();
+
+        let lines: Vec<_> = text
+            .lines()
+            .map(|line| {
+                let mut x = 0.;
+                let glyphs: Vec<_> = line
+                    .chars()
+                    .map(|c| {
+                        let id = charmap.map(c).unwrap_or(GlyphId::NOTDEF);
+                        let glyph = Glyph {
+                            id: id.to_u32(),
+                            x,
+                            y: 0.,
+                        };
+                        x += glyph_metrics.advance_width(id).unwrap_or_default();
+                        glyph
+                    })
+                    .collect();
+                (glyphs, x as f64)
+            })
+            .collect();
+
+        let line_height = (metrics.ascent - metrics.descent + metrics.leading) as f64;
+        Self {
+            width: lines.iter().map(|(_, width)| *width).fold(0., f64::max),
+            height: lines.len() as f64 * line_height,
+            line_height,
+            ascent: metrics.ascent as f64,
+            lines,
+        }
+    }
+
+    /// Draws the text with the top left of its box at the origin of
+    /// `transform`, aligning each line within the box.
+    pub(super) fn draw(
+        self,
+        scene: &mut Scene,
+        font: &Font,
+        size: f32,
+        transform: Affine,
+        align: HorizontalAlign,
+        colour: [f32; 4],
+    ) {
+        for (i, (glyphs, line_width)) in self.lines.into_iter().enumerate() {
+            let x = match align {
+                HorizontalAlign::Left => 0.,
+                HorizontalAlign::Centre => (self.width - line_width) / 2.,
+                HorizontalAlign::Right => self.width - line_width,
+            };
+            let baseline = i as f64 * self.line_height + self.ascent;
+
+            scene
+                .draw_glyphs(font.data())
+                .font_size(size)
+                .transform(transform * Affine::translate((x, baseline)))
+                .brush(Color::new(colour))
+                .hint(false)
+                .draw(Fill::NonZero, glyphs.into_iter());
+        }
+    }
+}
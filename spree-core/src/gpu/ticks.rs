@@ -0,0 +1,249 @@
+use std::f64::consts::TAU;
+
+use vello::{
+    Scene,
+    kurbo::{Affine, Line, Point, Rect, Stroke, Vec2},
+    peniko::Color,
+};
+
+use super::{SceneParams, text::TextLayout};
+use crate::{
+    layer::{Axis, HorizontalAlign, Side, Ticks},
+    layout::PlotInstanceLayout,
+};
+
+/// The most ticks drawn on one axis, in case the spacing is tiny compared to
+/// the view.
+const MAX_TICKS: i64 = 1000;
+
+/// Builds a vello scene drawing `ticks` and their labels at the current view.
+///
+/// `scale` is the number of physical pixels per logical pixel.
+pub(super) fn build_scene(ticks: &[Ticks<'_>], layout: &PlotInstanceLayout, scale: f64) -> Scene {
+    let mut scene = Scene::new();
+    let inner = Rect::new(
+        layout.padding.left,
+        layout.padding.top,
+        layout.logical_width - layout.padding.right,
+        layout.logical_height - layout.padding.bottom,
+    );
+    for ticks in ticks {
+        draw_ticks(&mut scene, ticks, layout, inner, Affine::scale(scale));
+    }
+    scene
+}
+
+fn draw_ticks(
+    scene: &mut Scene,
+    ticks: &Ticks<'_>,
+    layout: &PlotInstanceLayout,
+    inner: Rect,
+    transform: Affine,
+) {
+    let side = match ticks.axis {
+        Axis::X => Side::Left,
+        Axis::Y => ticks.side,
+    };
+    let scene_params = layout.scene_params_for(side);
+    if ticks.spacing <= 0. {
+        return;
+    }
+    if scene_params.is_polar() {
+        draw_polar_ticks(scene, ticks, &scene_params, inner, transform);
+        return;
+    }
+
+    let to_pixels = scene_params.pixel_transform();
+    let from_pixels = to_pixels.inverse();
+    let (start, end) = match ticks.axis {
+        Axis::X => (
+            (from_pixels * Point::new(inner.x0, 0.)).x,
+            (from_pixels * Point::new(inner.x1, 0.)).x,
+        ),
+        Axis::Y => (
+            (from_pixels * Point::new(0., inner.y1)).y,
+            (from_pixels * Point::new(0., inner.y0)).y,
+        ),
+    };
+    let first = (start / ticks.spacing).ceil() as i64;
+    let last = ((end / ticks.spacing).floor() as i64).min(first + MAX_TICKS);
+    let decimals = decimals(ticks.spacing);
+
+    for i in first..=last {
+        let value = i as f64 * ticks.spacing;
+        let (point, direction) = match (ticks.axis, side) {
+            (Axis::X, _) => (
+                Point::new((to_pixels * Point::new(value, 0.)).x, inner.y1),
+                Vec2::new(0., 1.),
+            ),
+            (Axis::Y, Side::Left) => (
+                Point::new(inner.x0, (to_pixels * Point::new(0., value)).y),
+                Vec2::new(-1., 0.),
+            ),
+            (Axis::Y, Side::Right) => (
+                Point::new(inner.x1, (to_pixels * Point::new(0., value)).y),
+                Vec2::new(1., 0.),
+            ),
+        };
+        let label = format_tick(value, decimals);
+        draw_tick(scene, ticks, point, direction, &label, transform);
+    }
+}
+
+/// Draws the ticks of a polar plot, with theta ticks in degrees around the
+/// largest circle inside the padding and r ticks along the ray at theta = 0.
+fn draw_polar_ticks(
+    scene: &mut Scene,
+    ticks: &Ticks<'_>,
+    scene_params: &SceneParams,
+    inner: Rect,
+    transform: Affine,
+) {
+    let to_pixels = scene_params.pixel_transform();
+    let centre = to_pixels * Point::ORIGIN;
+    let radius = [
+        centre.x - inner.x0,
+        inner.x1 - centre.x,
+        centre.y - inner.y0,
+        inner.y1 - centre.y,
+    ]
+    .into_iter()
+    .fold(f64::INFINITY, f64::min);
+    if radius <= 0. {
+        return;
+    }
+    let plane_radius = radius / to_pixels.as_coeffs()[0].abs();
+
+    match ticks.axis {
+        Axis::X => {
+            let decimals = decimals(rounded(ticks.spacing.to_degrees()));
+            for value in angles(ticks.spacing) {
+                let on_circle = scene_params.map_to_plane(Point::new(value, plane_radius));
+                let point = to_pixels * on_circle;
+                let direction = (point - centre).normalize();
+                let label = format!("{}°", format_tick(value.to_degrees(), decimals));
+                draw_tick(scene, ticks, point, direction, &label, transform);
+            }
+        }
+        Axis::Y => {
+            let ray = to_pixels * scene_params.map_to_plane(Point::new(0., 1.)) - centre;
+            // Labels hang below the ray, or above it when it points down.
+            let mut direction = Vec2::new(ray.y, -ray.x).normalize();
+            if direction.y < 0. {
+                direction = -direction;
+            }
+            let decimals = decimals(ticks.spacing);
+            let last = ((plane_radius / ticks.spacing).floor() as i64).min(MAX_TICKS);
+            for i in 1..=last {
+                let value = i as f64 * ticks.spacing;
+                let point = to_pixels * scene_params.map_to_plane(Point::new(0., value));
+                let label = format_tick(value, decimals);
+                draw_tick(scene, ticks, point, direction, &label, transform);
+            }
+        }
+    }
+}
+
+/// Draws a tick from `point` in `direction`, with `label` just beyond it.
+fn draw_tick(
+    scene: &mut Scene,
+    ticks: &Ticks<'_>,
+    point: Point,
+    direction: Vec2,
+    label: &str,
+    transform: Affine,
+) {
+    let tick_end = point + direction * ticks.tick_length as f64;
+    scene.stroke(
+        &Stroke::new(1.),
+        transform,
+        Color::new(ticks.colour),
+        None,
+        &Line::new(point, tick_end),
+    );
+
+    // Labels sit on the far side of a point just past the tick, so they are
+    // centred along axes and move out from circles.
+    let label = TextLayout::new(label, ticks.font, ticks.size);
+    let anchor = tick_end + direction * (ticks.size as f64 / 4.);
+    let origin = Vec2::new(
+        (direction.x - 1.) * label.width / 2.,
+        (direction.y - 1.) * label.height / 2.,
+    );
+    label.draw(
+        scene,
+        ticks.font,
+        ticks.size,
+        transform * Affine::translate(anchor.to_vec2() + origin),
+        HorizontalAlign::Left,
+        ticks.colour,
+    );
+}
+
+/// The multiples of `spacing` in a full turn, starting at zero.
+fn angles(spacing: f64) -> impl Iterator<Item = f64> {
+    let count = (TAU / spacing - 1e-9).ceil().min(MAX_TICKS as f64) as i64;
+    (0..count).map(move |i| i as f64 * spacing)
+}
+
+/// Drops the error from converting to degrees, so that a spacing of π / 4
+/// reads as 45.
+fn rounded(value: f64) -> f64 {
+    (value * 1e9).round() / 1e9
+}
+
+/// The decimal places needed to show every multiple of `spacing`, taken from
+/// the shortest form of `spacing` that reads back as the same number.
+fn decimals(spacing: f64) -> usize {
+    spacing
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+fn format_tick(value: f64, decimals: usize) -> String {
+    // Adding zero turns negative zero into zero.
+    format!("{:.*}", decimals, value + 0.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimals_follow_significant_digits() {
+        assert_eq!(decimals(10.), 0);
+        assert_eq!(decimals(2.5), 1);
+        assert_eq!(decimals(0.1), 1);
+        assert_eq!(decimals(0.25), 2);
+        assert_eq!(decimals(0.005), 3);
+    }
+
+    #[test]
+    fn labels_show_every_multiple() {
+        let labels: Vec<_> = (-2..=3)
+            .map(|i| format_tick(i as f64 * 0.25, decimals(0.25)))
+            .collect();
+        assert_eq!(labels, ["-0.50", "-0.25", "0.00", "0.25", "0.50", "0.75"]);
+
+        let labels: Vec<_> = (1..=3)
+            .map(|i| format_tick(i as f64 * 2.5, decimals(2.5)))
+            .collect();
+        assert_eq!(labels, ["2.5", "5.0", "7.5"]);
+
+        assert_eq!(format_tick(3. * 0.1, decimals(0.1)), "0.3");
+        assert_eq!(format_tick(-0., 1), "0.0");
+    }
+
+    #[test]
+    fn angles_cover_one_turn() {
+        let labels: Vec<_> = angles(TAU / 8.)
+            .map(|angle| {
+                let decimals = decimals(rounded((TAU / 8.).to_degrees()));
+                format_tick(angle.to_degrees(), decimals)
+            })
+            .collect();
+        assert_eq!(labels, ["0", "45", "90", "135", "180", "225", "270", "315"]);
+        assert_eq!(angles(1.).count(), 7);
+    }
+}
@@ -0,0 +1,58 @@
+use vello::{
+    Scene,
+    kurbo::{Affine, Point, Rect, Vec2},
+    peniko::{Color, Fill},
+};
+
+use super::text::TextLayout;
+use crate::layer::{HorizontalAlign, Tooltip};
+
+/// The distance in logical pixels from the mouse to the tooltip.
+const OFFSET: f64 = 12.;
+
+/// Builds a vello scene drawing `text` in a box above and to the right of
+/// `cursor`, moved inside the viewport if it would overflow. `cursor` is in
+/// logical pixels from the top left.
+///
+/// `scale` is the number of physical pixels per logical pixel.
+pub(super) fn build_scene(
+    tooltip: &Tooltip<'_>,
+    text: &str,
+    cursor: Point,
+    viewport_size: [f32; 2],
+    scale: f64,
+) -> Scene {
+    let mut scene = Scene::new();
+    let label = TextLayout::new(text, tooltip.font, tooltip.size);
+    let padding = tooltip.size as f64 / 4.;
+    let (width, height) = (label.width + 2. * padding, label.height + 2. * padding);
+
+    let [viewport_width, viewport_height] = viewport_size.map(f64::from);
+    let mut origin = cursor + Vec2::new(OFFSET, -OFFSET - height);
+    if origin.x + width > viewport_width {
+        origin.x = cursor.x - OFFSET - width;
+    }
+    if origin.y < 0. {
+        origin.y = cursor.y + OFFSET;
+    }
+    origin.x = origin.x.clamp(0., (viewport_width - width).max(0.));
+    origin.y = origin.y.clamp(0., (viewport_height - height).max(0.));
+
+    let transform = Affine::scale(scale) * Affine::translate(origin.to_vec2());
+    scene.fill(
+        Fill::NonZero,
+        transform,
+        Color::new(tooltip.background),
+        None,
+        &Rect::new(0., 0., width, height),
+    );
+    label.draw(
+        &mut scene,
+        tooltip.font,
+        tooltip.size,
+        transform * Affine::translate((padding, padding)),
+        HorizontalAlign::Left,
+        tooltip.colour,
+    );
+    scene
+}
@@ -0,0 +1,53 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use crate::layer::{DENSITY_POINTS, Orientation, Violin};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerViolinParams {
+    colour: [f32; 4],
+    range: [f32; 2],
+    position: f32,
+    width: f32,
+    count: u32,
+    orientation: u32,
+    _padding: [u32; 2],
+}
+
+pub(super) struct Renderer {}
+
+impl super::LayerRenderer for Renderer {
+    type Layer<'a> = Violin<'a>;
+    type PerLayerParams = PerViolinParams;
+
+    const NAME: &'static str = "violin";
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["polar.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, _: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        // A strip across the violin at each value the density is known at.
+        (0..DENSITY_POINTS as u32 * 2, 0..1)
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        PerViolinParams {
+            colour: data.colour,
+            range: data.data.range(),
+            position: data.position,
+            width: data.width,
+            count: DENSITY_POINTS as u32,
+            orientation: match data.orientation {
+                Orientation::Vertical => 0,
+                Orientation::Horizontal => 1,
+            },
+            _padding: [0, 0],
+        }
+    }
+}
@@ -0,0 +1,47 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+const ORIENTATION_HORIZONTAL: u32 = 1u;
+
+struct Params {
+    colour: vec4<f32>,
+    // The values the density is known between.
+    range: vec2<f32>,
+    position: f32,
+    width: f32,
+    count: u32,
+    orientation: u32,
+    _pad_0: u32,
+    _pad_1: u32,
+}
+// The density at `count` evenly spaced values, scaled so the largest is one.
+@group(1) @binding(0) var<storage, read> density: array<f32>;
+@group(1) @binding(1) var<uniform> params: Params;
+
+// Alternates between the left and right edges, moving up through the values.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let k = vertex_index / 2u;
+    let side = f32(vertex_index & 1u) * 2.0 - 1.0;
+    let t = f32(k) / f32(params.count - 1u);
+
+    let value = mix(params.range.x, params.range.y, t);
+    let offset = side * density[k] * params.width / 2.0;
+    var point = vec2<f32>(params.position + offset, value);
+    if (params.orientation == ORIENTATION_HORIZONTAL) {
+        point = point.yx;
+    }
+
+    return scene.projection_matrix * vec4<f32>(to_plane(point), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return params.colour;
+}
@@ -0,0 +1,123 @@
+use bytemuck::{Pod, Zeroable};
+use vello::wgpu;
+
+use super::{LayerRenderer, SceneParams, colormap_buffer, to_buffer};
+use crate::layer::{Axis, Waterfall};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub(super) struct PerWaterfallParams {
+    nan_colour: [f32; 4],
+    time_min: f32,
+    time_max: f32,
+    bins_min: f32,
+    bins_max: f32,
+    range_min: f32,
+    range_max: f32,
+    oldest: u32,
+    count: u32,
+    time_axis: u32,
+    _padding: [u32; 3],
+}
+
+pub(super) struct Renderer {}
+
+impl LayerRenderer for Renderer {
+    type Layer<'a> = Waterfall<'a>;
+    type PerLayerParams = PerWaterfallParams;
+
+    const NAME: &'static str = "waterfall";
+
+    fn new(_: &wgpu::Device) -> Self {
+        Self {}
+    }
+
+    fn shader(&self) -> wgpu::ShaderModuleDescriptor<'static> {
+        include_wgsl_with!(["gaps.wgsl", "colormap.wgsl"], "render.wgsl")
+    }
+
+    fn counts(&self, data: &Self::Layer<'_>) -> (std::ops::Range<u32>, std::ops::Range<u32>) {
+        (0..4, 0..u32::from(!data.data.is_empty()))
+    }
+
+    fn per_layer_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("{} group 1 layout", Self::NAME)),
+            entries: &[
+                Self::_WITHOUT_POINTS[0],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    ..Self::_WITH_POINTS[0]
+                },
+            ],
+        })
+    }
+
+    fn create_per_layer_params<'a>(&self, data: &Self::Layer<'a>) -> Self::PerLayerParams {
+        // The times can grow large, so the subtraction is done in double
+        // precision to avoid cancellation. Both ends are still rounded to
+        // single precision for the shader.
+        let newest = data.start + data.step * data.data.pushed() as f64;
+        let oldest = newest - data.step * data.data.len() as f64;
+
+        PerWaterfallParams {
+            nan_colour: data.nan_colour,
+            time_min: oldest as f32,
+            time_max: newest as f32,
+            bins_min: data.bins.min as f32,
+            bins_max: data.bins.max as f32,
+            range_min: data.range.min as f32,
+            range_max: data.range.max as f32,
+            oldest: data.data.oldest(),
+            count: data.data.len() as u32,
+            time_axis: match data.time_axis {
+                Axis::X => 0,
+                Axis::Y => 1,
+            },
+            _padding: [0; 3],
+        }
+    }
+
+    fn create_per_layer_group<'a>(
+        &self,
+        device: &wgpu::Device,
+        _: &mut wgpu::CommandEncoder,
+        _: &SceneParams,
+        layout: &wgpu::BindGroupLayout,
+        layer: &Self::Layer<'a>,
+    ) -> wgpu::BindGroup {
+        let name = format!("{} bind group 1", Self::NAME);
+        let params = self.create_per_layer_params(layer);
+        let params_buffer = to_buffer(device, &name, &params);
+        let colormap = colormap_buffer(device, &name, &layer.colormap);
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&name),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: layer.data.as_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: colormap.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
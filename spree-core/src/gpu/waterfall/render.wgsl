@@ -0,0 +1,72 @@
+struct SceneParams {
+    projection_matrix: mat4x4<f32>,
+    inverse_projection_matrix: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    polar: f32,
+    rotation: f32,
+};
+@group(0) @binding(0) var<uniform> scene: SceneParams;
+
+struct Params {
+    nan_colour: vec4<f32>,
+    // The times of the start of the oldest column and the end of the newest.
+    time_min: f32,
+    time_max: f32,
+    bins_min: f32,
+    bins_max: f32,
+    range_min: f32,
+    range_max: f32,
+    // The row of the ring texture holding the oldest column.
+    oldest: u32,
+    count: u32,
+    time_axis: u32,
+    _pad_0: u32,
+    _pad_1: u32,
+    _pad_2: u32,
+}
+@group(1) @binding(0) var<uniform> params: Params;
+// Each row holds one column of the waterfall.
+@group(1) @binding(1) var ring: texture_2d<f32>;
+@group(1) @binding(2) var<storage, read> colormap: array<vec4<f32>>;
+
+const AXIS_X: u32 = 0u;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // The position along the time axis and then across the bins, from zero to
+    // one.
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u));
+    var point = mix(
+        vec2<f32>(params.time_min, params.bins_min),
+        vec2<f32>(params.time_max, params.bins_max),
+        uv
+    );
+    if (params.time_axis != AXIS_X) {
+        point = point.yx;
+    }
+
+    var out: VertexOutput;
+    out.position = scene.projection_matrix * vec4<f32>(point, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = textureDimensions(ring);
+    let column = min(u32(in.uv.x * f32(params.count)), params.count - 1u);
+    let bin = min(u32(in.uv.y * f32(size.x)), size.x - 1u);
+    let value = textureLoad(ring, vec2<u32>(bin, (params.oldest + column) % size.y), 0).r;
+    if (is_nan(value)) {
+        return params.nan_colour;
+    }
+
+    // An empty range maps every value to the start of the colormap.
+    let span = params.range_max - params.range_min;
+    return sample_colormap(select(0.0, (value - params.range_min) / span, span != 0.0));
+}
@@ -0,0 +1,79 @@
+/// Maps values between zero and one to colours.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Colormap<'a> {
+    #[default]
+    Viridis,
+    Magma,
+    Coolwarm,
+    Grayscale,
+    /// Colours evenly spaced from zero to one, interpolated linearly.
+    Custom(&'a [[f32; 4]]),
+}
+
+// Samples of matplotlib's colormaps at every tenth, or every eighth for
+// coolwarm.
+const VIRIDIS: [u32; 11] = [
+    0x440154, 0x482475, 0x414487, 0x355f8d, 0x2a788e, 0x21918c, 0x22a884, 0x44bf70, 0x7ad151,
+    0xbddf26, 0xfde725,
+];
+const MAGMA: [u32; 11] = [
+    0x000004, 0x140e36, 0x3b0f70, 0x641a80, 0x8c2981, 0xb73779, 0xde4968, 0xf7705c, 0xfe9f6d,
+    0xfecf92, 0xfcfdbf,
+];
+const COOLWARM: [u32; 9] = [
+    0x3b4cc0, 0x6282ea, 0x8db0fe, 0xb8d0f9, 0xdddddd, 0xf5c4ad, 0xf49a7b, 0xde604d, 0xb40426,
+];
+const GRAYSCALE: [u32; 2] = [0x000000, 0xffffff];
+
+impl Colormap<'_> {
+    pub(crate) fn stops(&self) -> Vec<[f32; 4]> {
+        let hex: &[u32] = match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+            Colormap::Coolwarm => &COOLWARM,
+            Colormap::Grayscale => &GRAYSCALE,
+            Colormap::Custom(stops) => {
+                assert!(!stops.is_empty(), "colormaps need at least one colour");
+                return stops.to_vec();
+            }
+        };
+
+        hex.iter()
+            .map(|colour| {
+                let [_, r, g, b] = colour.to_be_bytes();
+                [r, g, b, 255].map(|channel| f32::from(channel) / 255.)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_colormaps_unpack_hex_colours() {
+        assert_eq!(
+            Colormap::Grayscale.stops(),
+            [[0., 0., 0., 1.], [1., 1., 1., 1.]]
+        );
+
+        let viridis = Colormap::Viridis.stops();
+        assert_eq!(viridis.len(), 11);
+        assert_eq!(viridis[0], [68. / 255., 1. / 255., 84. / 255., 1.]);
+        assert_eq!(viridis[10], [253. / 255., 231. / 255., 37. / 255., 1.]);
+        assert_eq!(Colormap::Coolwarm.stops().len(), 9);
+    }
+
+    #[test]
+    fn custom_colormaps_keep_their_stops() {
+        let stops = [[1., 0., 0., 0.5], [0., 0., 1., 1.]];
+        assert_eq!(Colormap::Custom(&stops).stops(), stops);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one colour")]
+    fn custom_colormaps_need_a_colour() {
+        Colormap::Custom(&[]).stops();
+    }
+}
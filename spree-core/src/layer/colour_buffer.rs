@@ -0,0 +1,49 @@
+use vello::wgpu::{self, CommandBuffer};
+
+use crate::gpu::GpuBuffer;
+
+/// An RGBA colour for each point of a [`PointBuffer`](super::PointBuffer),
+/// matched to the points by index.
+#[derive(Debug)]
+pub struct ColourBuffer {
+    inner: GpuBuffer<f32>,
+}
+
+impl ColourBuffer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            inner: GpuBuffer::new(
+                device,
+                wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::STORAGE,
+                0,
+                |_| {},
+            ),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[must_use]
+    pub fn append(&mut self, colour: [f32; 4], device: &wgpu::Device) -> CommandBuffer {
+        self.extend(&[colour], device)
+    }
+
+    #[must_use]
+    pub fn extend(&mut self, colours: &[[f32; 4]], device: &wgpu::Device) -> CommandBuffer {
+        self.inner.extend(device, colours.len() * 4, |buffer| {
+            buffer.copy_from_slice(colours.as_flattened())
+        })
+    }
+
+    pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.inner.as_entire_binding()
+    }
+}
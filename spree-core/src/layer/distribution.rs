@@ -0,0 +1,250 @@
+use vello::wgpu::{self, util::DeviceExt};
+
+/// The number of values the density of a [`Distribution`] is estimated at.
+pub(crate) const DENSITY_POINTS: usize = 64;
+
+/// Summary statistics and a density estimate of a set of samples, drawn by
+/// [`BoxPlot`](super::BoxPlot) and [`Violin`](super::Violin).
+///
+/// The statistics are computed on the CPU when the distribution is created.
+#[derive(Debug)]
+pub struct Distribution {
+    quartiles: [f32; 3],
+    whiskers: [f32; 2],
+    range: [f32; 2],
+    outliers: wgpu::Buffer,
+    outlier_count: usize,
+    density: wgpu::Buffer,
+}
+
+impl Distribution {
+    /// Computes the statistics of `samples`, ignoring NaNs and infinities.
+    ///
+    /// Whiskers reach the furthest samples within 1.5 times the interquartile
+    /// range of the box, and samples beyond them are outliers. The density is
+    /// a Gaussian kernel density estimate using Silverman's rule of thumb.
+    ///
+    /// # Panics
+    ///
+    /// Panics if none of the samples are finite.
+    pub fn new(samples: &[f32], device: &wgpu::Device) -> Self {
+        let statistics =
+            Statistics::new(samples).expect("a distribution needs at least one finite sample");
+
+        Self {
+            quartiles: statistics.quartiles,
+            whiskers: statistics.whiskers,
+            range: statistics.range,
+            outlier_count: statistics.outliers.len(),
+            outliers: storage_buffer(device, "distribution outliers", &statistics.outliers),
+            density: storage_buffer(device, "distribution density", &statistics.density),
+        }
+    }
+
+    /// The lower quartile, median and upper quartile.
+    pub fn quartiles(&self) -> [f32; 3] {
+        self.quartiles
+    }
+
+    /// The ends of the lower and upper whiskers.
+    pub fn whiskers(&self) -> [f32; 2] {
+        self.whiskers
+    }
+
+    /// The smallest and largest samples.
+    pub fn range(&self) -> [f32; 2] {
+        self.range
+    }
+
+    pub fn outlier_count(&self) -> usize {
+        self.outlier_count
+    }
+
+    pub(crate) fn outliers_binding(&self) -> wgpu::BindingResource<'_> {
+        self.outliers.as_entire_binding()
+    }
+
+    /// The density at evenly spaced values across the range, scaled so the
+    /// largest is one.
+    pub(crate) fn density_binding(&self) -> wgpu::BindingResource<'_> {
+        self.density.as_entire_binding()
+    }
+}
+
+/// The statistics of a [`Distribution`] before they are uploaded.
+#[derive(Debug, PartialEq)]
+struct Statistics {
+    quartiles: [f32; 3],
+    whiskers: [f32; 2],
+    range: [f32; 2],
+    outliers: Vec<f32>,
+    density: Vec<f32>,
+}
+
+impl Statistics {
+    /// Returns `None` if none of the samples are finite.
+    fn new(samples: &[f32]) -> Option<Self> {
+        let mut sorted: Vec<_> = samples.iter().copied().filter(|x| x.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f32::total_cmp);
+
+        let quartiles = [0.25, 0.5, 0.75].map(|q| quantile(&sorted, q));
+        let iqr = quartiles[2] - quartiles[0];
+        let fence = [quartiles[0] - 1.5 * iqr, quartiles[2] + 1.5 * iqr];
+        let inside = |x: &&f32| **x >= fence[0] && **x <= fence[1];
+        let whiskers = [
+            *sorted.iter().find(inside).unwrap_or(&quartiles[0]),
+            *sorted.iter().rev().find(inside).unwrap_or(&quartiles[2]),
+        ];
+        let outliers = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x < whiskers[0] || *x > whiskers[1])
+            .collect();
+        let range = [sorted[0], sorted[sorted.len() - 1]];
+
+        Some(Self {
+            quartiles,
+            whiskers,
+            range,
+            outliers,
+            density: density(&sorted, range, bandwidth(&sorted, iqr)),
+        })
+    }
+}
+
+/// Linearly interpolates between the closest ranks of sorted values.
+fn quantile(sorted: &[f32], q: f32) -> f32 {
+    let rank = q * (sorted.len() - 1) as f32;
+    let below = rank.floor() as usize;
+    let above = (below + 1).min(sorted.len() - 1);
+    sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f32)
+}
+
+/// The bandwidth from Silverman's rule of thumb.
+fn bandwidth(sorted: &[f32], iqr: f32) -> f32 {
+    let n = sorted.len() as f32;
+    let mean = sorted.iter().sum::<f32>() / n;
+    let deviation = (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / n).sqrt();
+    let spread = if iqr > 0. {
+        deviation.min(iqr / 1.34)
+    } else {
+        deviation
+    };
+    // Identical samples still get a visible, if narrow, bump.
+    if spread > 0. {
+        0.9 * spread * n.powf(-0.2)
+    } else {
+        1.
+    }
+}
+
+fn density(sorted: &[f32], range: [f32; 2], bandwidth: f32) -> Vec<f32> {
+    let densities: Vec<_> = (0..DENSITY_POINTS)
+        .map(|i| {
+            let t = i as f32 / (DENSITY_POINTS - 1) as f32;
+            let at = range[0] + (range[1] - range[0]) * t;
+            sorted
+                .iter()
+                .map(|x| (-0.5 * ((at - x) / bandwidth).powi(2)).exp())
+                .sum::<f32>()
+        })
+        .collect();
+
+    let largest = densities.iter().copied().fold(0., f32::max);
+    densities.into_iter().map(|d| d / largest).collect()
+}
+
+/// Uploads values for a shader to read, padding empty arrays as bindings can't
+/// be empty.
+fn storage_buffer(device: &wgpu::Device, name: &str, values: &[f32]) -> wgpu::Buffer {
+    let values = if values.is_empty() { &[0.][..] } else { values };
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(name),
+        contents: bytemuck::cast_slice(values),
+        usage: wgpu::BufferUsages::STORAGE,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_TO_NINE: [f32; 9] = [1., 2., 3., 4., 5., 6., 7., 8., 9.];
+
+    #[test]
+    fn quantiles_interpolate_between_ranks() {
+        assert_eq!(quantile(&ONE_TO_NINE, 0.), 1.);
+        assert_eq!(quantile(&ONE_TO_NINE, 0.25), 3.);
+        assert_eq!(quantile(&ONE_TO_NINE, 0.5), 5.);
+        assert_eq!(quantile(&ONE_TO_NINE, 0.75), 7.);
+        assert_eq!(quantile(&ONE_TO_NINE, 1.), 9.);
+        assert!((quantile(&ONE_TO_NINE, 0.1) - 1.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn samples_past_the_fences_are_outliers() {
+        let mut samples = ONE_TO_NINE.to_vec();
+        samples.extend([30., -20.]);
+        let statistics = Statistics::new(&samples).unwrap();
+
+        // The fences are 1.5 interquartile ranges of 5 past 2.5 and 7.5.
+        assert_eq!(statistics.quartiles, [2.5, 5., 7.5]);
+        assert_eq!(statistics.whiskers, [1., 9.]);
+        assert_eq!(statistics.outliers, [-20., 30.]);
+        assert_eq!(statistics.range, [-20., 30.]);
+    }
+
+    #[test]
+    fn bandwidth_follows_silvermans_rule() {
+        // The deviation is smaller than the interquartile range over 1.34.
+        let deviation = (60f32 / 9.).sqrt();
+        let expected = 0.9 * deviation * 9f32.powf(-0.2);
+        assert!((bandwidth(&ONE_TO_NINE, 4.) - expected).abs() < 1e-5);
+
+        // A narrow interquartile range limits the spread instead.
+        let expected = 0.9 * (1. / 1.34) * 9f32.powf(-0.2);
+        assert!((bandwidth(&ONE_TO_NINE, 1.) - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn density_peaks_in_the_middle_of_symmetric_samples() {
+        let density = Statistics::new(&ONE_TO_NINE).unwrap().density;
+        assert_eq!(density.len(), DENSITY_POINTS);
+        let largest = density.iter().copied().fold(0., f32::max);
+        assert_eq!(largest, 1.);
+        assert!((density[0] - density[DENSITY_POINTS - 1]).abs() < 1e-5);
+        assert!(density[DENSITY_POINTS / 2] > density[0]);
+    }
+
+    #[test]
+    fn a_single_sample_summarises_to_itself() {
+        let statistics = Statistics::new(&[3.]).unwrap();
+        assert_eq!(statistics.quartiles, [3.; 3]);
+        assert_eq!(statistics.whiskers, [3.; 2]);
+        assert_eq!(statistics.range, [3.; 2]);
+        assert!(statistics.outliers.is_empty());
+        assert!(statistics.density.iter().all(|d| *d == 1.));
+    }
+
+    #[test]
+    fn constant_samples_get_a_unit_bandwidth() {
+        let statistics = Statistics::new(&[2.; 5]).unwrap();
+        assert_eq!(statistics.quartiles, [2.; 3]);
+        assert!(statistics.outliers.is_empty());
+        assert_eq!(bandwidth(&[2.; 5], 0.), 1.);
+    }
+
+    #[test]
+    fn samples_that_are_not_finite_are_ignored() {
+        let samples = [1., f32::INFINITY, f32::NAN, -f32::INFINITY, 2.];
+        let statistics = Statistics::new(&samples).unwrap();
+        assert_eq!(statistics.range, [1., 2.]);
+        assert!(statistics.outliers.is_empty());
+
+        assert_eq!(Statistics::new(&[f32::NAN, f32::INFINITY]), None);
+        assert_eq!(Statistics::new(&[]), None);
+    }
+}
@@ -0,0 +1,88 @@
+use vello::wgpu::{self, CommandBuffer};
+
+use crate::gpu::GpuBuffer;
+
+/// The uncertainty in each point of a [`PointBuffer`](super::PointBuffer),
+/// matched to the points by index.
+#[derive(Debug)]
+pub struct ErrorBuffer {
+    inner: GpuBuffer<f32>,
+}
+
+/// The uncertainty in one coordinate of a point.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Uncertainty {
+    #[default]
+    None,
+    Symmetric(f32),
+    Asymmetric {
+        minus: f32,
+        plus: f32,
+    },
+}
+
+impl Uncertainty {
+    fn bounds(self) -> [f32; 2] {
+        match self {
+            Uncertainty::None => [0., 0.],
+            Uncertainty::Symmetric(error) => [error, error],
+            Uncertainty::Asymmetric { minus, plus } => [minus, plus],
+        }
+    }
+}
+
+impl ErrorBuffer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            inner: GpuBuffer::new(
+                device,
+                wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::STORAGE,
+                0,
+                |_| {},
+            ),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[must_use]
+    pub fn append(
+        &mut self,
+        x: Uncertainty,
+        y: Uncertainty,
+        device: &wgpu::Device,
+    ) -> CommandBuffer {
+        self.extend(&[x], &[y], device)
+    }
+
+    #[must_use]
+    pub fn extend(
+        &mut self,
+        xs: &[Uncertainty],
+        ys: &[Uncertainty],
+        device: &wgpu::Device,
+    ) -> CommandBuffer {
+        assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+
+        let len = xs.len();
+        self.inner.extend(device, len * 4, |buffer| {
+            for i in 0..len {
+                let [x_minus, x_plus] = xs[i].bounds();
+                let [y_minus, y_plus] = ys[i].bounds();
+                buffer[i * 4..i * 4 + 4].copy_from_slice(&[x_minus, x_plus, y_minus, y_plus]);
+            }
+        })
+    }
+
+    pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.inner.as_entire_binding()
+    }
+}
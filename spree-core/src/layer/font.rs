@@ -0,0 +1,30 @@
+use vello::{
+    peniko::{Blob, FontData},
+    skrifa::FontRef,
+};
+
+/// A font for drawing text, loaded from a TrueType or OpenType file.
+#[derive(Debug, Clone)]
+pub struct Font {
+    data: FontData,
+}
+
+impl Font {
+    /// Loads a font from the contents of a font file, or the first font in a
+    /// collection. Returns `None` if the data isn't a font.
+    pub fn new(data: Vec<u8>) -> Option<Self> {
+        let data = FontData::new(Blob::from(data), 0);
+        FontRef::from_index(data.data.data(), data.index).ok()?;
+
+        Some(Self { data })
+    }
+
+    pub(crate) fn data(&self) -> &FontData {
+        &self.data
+    }
+
+    pub(crate) fn font_ref(&self) -> FontRef<'_> {
+        FontRef::from_index(self.data.data.data(), self.data.index)
+            .expect("fonts are checked when they are loaded")
+    }
+}
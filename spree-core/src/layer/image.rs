@@ -0,0 +1,136 @@
+use vello::wgpu::{self, CommandBuffer};
+
+/// A grid of values stored in a GPU texture, drawn by a
+/// [`Heatmap`](super::Heatmap).
+///
+/// A copy of the values is kept on the CPU for placing contour labels.
+#[derive(Debug)]
+pub struct Image {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    values: Vec<f32>,
+}
+
+impl Image {
+    /// Creates an image of `width` by `height` cells, all set to zero.
+    pub fn new(width: u32, height: u32, device: &wgpu::Device) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("image"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R32Float,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            texture,
+            view,
+            values: vec![0.; (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+
+    /// Replaces every value in the image.
+    ///
+    /// `values` holds the rows in order, starting from the row with the
+    /// smallest y. NaN values are drawn in the heatmap's `nan_colour`.
+    #[must_use]
+    pub fn write(&mut self, values: &[f32], device: &wgpu::Device) -> CommandBuffer {
+        assert_eq!(
+            values.len(),
+            (self.width() * self.height()) as usize,
+            "values must have one value per cell"
+        );
+
+        self.write_rows(values, 0, device)
+    }
+
+    /// Copies whole rows into the image, starting at row `first`.
+    pub(crate) fn write_rows(
+        &mut self,
+        values: &[f32],
+        first: u32,
+        device: &wgpu::Device,
+    ) -> CommandBuffer {
+        let width = self.width();
+        let height = values.len() as u32 / width;
+        let start = (first * width) as usize;
+        self.values[start..start + values.len()].copy_from_slice(values);
+
+        // Buffer to texture copies need each row to start at an aligned offset.
+        let row_size = (width as usize * size_of::<f32>())
+            .next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize);
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("image staging buffer"),
+            size: (row_size * height as usize) as u64,
+            usage: wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: true,
+        });
+        {
+            let mut view = staging.slice(..).get_mapped_range_mut();
+            for (row, values) in view
+                .chunks_exact_mut(row_size)
+                .zip(values.chunks_exact(width as usize))
+            {
+                let values: &[u8] = bytemuck::cast_slice(values);
+                row[..values.len()].copy_from_slice(values);
+            }
+        }
+        staging.unmap();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("image write encoder"),
+        });
+        encoder.copy_buffer_to_texture(
+            wgpu::TexelCopyBufferInfo {
+                buffer: &staging,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(row_size as u32),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: 0,
+                    y: first,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        encoder.finish()
+    }
+
+    /// The values in the same order as [`Image::write`] takes them.
+    pub(crate) fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    pub(crate) fn as_binding(&self) -> wgpu::BindingResource<'_> {
+        wgpu::BindingResource::TextureView(&self.view)
+    }
+}
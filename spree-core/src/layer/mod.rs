@@ -1,8 +1,32 @@
+mod colormap;
+mod colour_buffer;
+mod distribution;
+mod error_buffer;
+mod font;
+mod image;
+mod ohlc_buffer;
 mod point_buffer;
+mod scalar_buffer;
+mod waterfall_buffer;
 
+pub use colormap::Colormap;
+pub use colour_buffer::ColourBuffer;
+pub(crate) use distribution::DENSITY_POINTS;
+pub use distribution::Distribution;
+pub use error_buffer::{ErrorBuffer, Uncertainty};
+pub use font::Font;
+pub use image::Image;
+pub use ohlc_buffer::{Candle, OhlcBuffer};
 pub use point_buffer::PointBuffer;
+pub use scalar_buffer::ScalarBuffer;
+pub use waterfall_buffer::WaterfallBuffer;
+
+use std::num::{NonZeroU32, NonZeroUsize};
+
 use vello::wgpu;
 
+use crate::layout::{Bounds, Interval, Scale};
+
 #[derive(Debug, Clone)]
 pub enum Layer<'a> {
     Title(&'a str),
@@ -11,6 +35,26 @@ pub enum Layer<'a> {
     Grid(Grid),
     Lines(Vec<Line<'a>>),
     Scatters(Vec<Scatter<'a>>),
+    Areas(Vec<Area<'a>>),
+    Bars(Vec<Bars<'a>>),
+    Histograms(Vec<Histogram<'a>>),
+    ErrorBars(Vec<ErrorBars<'a>>),
+    Heatmaps(Vec<Heatmap<'a>>),
+    Waterfalls(Vec<Waterfall<'a>>),
+    Densities(Vec<Density<'a>>),
+    Histograms2d(Vec<Histogram2d<'a>>),
+    Contours(Vec<Contour<'a>>),
+    References(Vec<Reference>),
+    Annotations(Vec<Annotation<'a>>),
+    Legend(Legend<'a>),
+    Candles(Vec<Candles<'a>>),
+    BoxPlots(Vec<BoxPlot<'a>>),
+    Violins(Vec<Violin<'a>>),
+    Quivers(Vec<Quiver<'a>>),
+    Shapes(Vec<Shape<'a>>),
+    Stems(Vec<Stems<'a>>),
+    CategoricalAxes(Vec<CategoricalAxis<'a>>),
+    Ticks(Vec<Ticks<'a>>),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -18,6 +62,33 @@ pub struct Line<'a> {
     pub data: &'a PointBuffer,
     pub thickness: f32,
     pub colour: [f32; 4],
+    pub join: LineJoin,
+    pub cap: LineCap,
+    pub dash: Dash<'a>,
+    /// Draws the line as a step function rather than interpolating between
+    /// points.
+    pub step: Option<Step>,
+    /// The name shown in a [`Legend`].
+    pub label: Option<&'a str>,
+    /// The y axis that the data is plotted against.
+    pub y_axis: Side,
+}
+
+impl<'a> Line<'a> {
+    /// Creates a thin black line with round joins and caps.
+    pub fn new(data: &'a PointBuffer) -> Self {
+        Self {
+            data,
+            thickness: 1.,
+            colour: [0., 0., 0., 1.],
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            dash: Dash::default(),
+            step: None,
+            label: None,
+            y_axis: Side::Left,
+        }
+    }
 }
 
 impl crate::gpu::Layer for Line<'_> {
@@ -28,11 +99,144 @@ impl crate::gpu::Layer for Line<'_> {
     }
 }
 
+/// How two segments of a line are joined.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineJoin {
+    #[default]
+    Round,
+    /// Extends the outer edges of the segments until they meet.
+    ///
+    /// Like SVG's `stroke-miterlimit`, joins whose miter length exceeds `limit`
+    /// times the thickness are drawn as [`LineJoin::Bevel`] instead.
+    Miter {
+        limit: f32,
+    },
+    Bevel,
+}
+
+/// How the ends of a line, including either side of a gap, are drawn.
+///
+/// A point with gaps on both sides is drawn as its caps alone, so it is a dot
+/// with round caps, a square with square caps and left out with butt caps.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineCap {
+    #[default]
+    Round,
+    /// Ends the line exactly at its end point.
+    Butt,
+    /// Extends the line by half its thickness past its end point.
+    Square,
+}
+
+/// Where a step line changes value between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Steps to the next value at the current point's x.
+    Pre,
+    /// Steps halfway between the two points.
+    Mid,
+    /// Holds the current value until the next point's x.
+    Post,
+}
+
+/// The dash pattern of a line.
+///
+/// Dashes are measured along the line on screen, so the pattern stays the same
+/// size as the plot is zoomed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Dash<'a> {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+    DashDot,
+    /// A pattern of on and off lengths, checked by [`DashPattern::new`].
+    Custom(DashPattern<'a>),
+}
+
+/// The most lengths in a dash pattern, after repeating odd patterns.
+pub(crate) const MAX_DASHES: usize = 8;
+
+/// Alternating on and off lengths, in multiples of the line's thickness.
+///
+/// As in SVG, a list with an odd number of lengths is repeated to make it
+/// even, and the line's cap is applied to both ends of every dash.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DashPattern<'a>(&'a [f32]);
+
+impl<'a> DashPattern<'a> {
+    /// Returns `None` if there are no lengths, more than eight after
+    /// repeating, or any length isn't positive and finite.
+    pub fn new(lengths: &'a [f32]) -> Option<Self> {
+        let repeated = lengths.len() * (1 + lengths.len() % 2);
+        let valid = (1..=MAX_DASHES).contains(&repeated)
+            && lengths
+                .iter()
+                .all(|length| length.is_finite() && *length > 0.);
+        valid.then_some(Self(lengths))
+    }
+}
+
+impl Dash<'_> {
+    pub(crate) fn pattern(&self, cap: LineCap) -> Vec<f32> {
+        let lengths: &[f32] = match self {
+            Dash::Solid => &[],
+            Dash::Dashed => &[3.7, 1.6],
+            Dash::Dotted => &[1., 1.65],
+            Dash::DashDot => &[6.4, 1.6, 1., 1.6],
+            Dash::Custom(DashPattern(lengths)) => {
+                return if lengths.len() % 2 == 1 {
+                    [*lengths, *lengths].concat()
+                } else {
+                    lengths.to_vec()
+                };
+            }
+        };
+
+        // The named patterns describe what is visible, so make room for caps.
+        match cap {
+            LineCap::Butt => lengths.to_vec(),
+            LineCap::Round | LineCap::Square => lengths
+                .chunks(2)
+                .flat_map(|dash| [(dash[0] - 1.).max(0.), dash[1] + dash[0].min(1.)])
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Scatter<'a> {
     pub data: &'a PointBuffer,
     pub radius: f32,
     pub colour: [f32; 4],
+    pub marker: Marker,
+    /// Draws only the outline of each marker, this many pixels wide.
+    pub stroke: Option<f32>,
+    /// Colours each point individually instead of using `colour`.
+    pub colours: Option<PointColours<'a>>,
+    /// The radius of each point, used instead of `radius`.
+    pub radii: Option<&'a ScalarBuffer>,
+    /// The name shown in a [`Legend`].
+    pub label: Option<&'a str>,
+    /// The y axis that the data is plotted against.
+    pub y_axis: Side,
+}
+
+impl<'a> Scatter<'a> {
+    /// Creates small black circles.
+    pub fn new(data: &'a PointBuffer) -> Self {
+        Self {
+            data,
+            radius: 2.,
+            colour: [0., 0., 0., 1.],
+            marker: Marker::default(),
+            stroke: None,
+            colours: None,
+            radii: None,
+            label: None,
+            y_axis: Side::Left,
+        }
+    }
 }
 
 impl crate::gpu::Layer for Scatter<'_> {
@@ -43,9 +247,841 @@ impl crate::gpu::Layer for Scatter<'_> {
     }
 }
 
+/// The shape drawn at each point of a [`Scatter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Marker {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+    TriangleUp,
+    TriangleDown,
+    /// A diagonal cross, like an x.
+    Cross,
+    Plus,
+    Star,
+}
+
+/// A colour for each point, matched to the points by index.
+#[derive(Debug, Clone, Copy)]
+pub enum PointColours<'a> {
+    /// Maps a value for each point through a colormap.
+    Mapped {
+        values: &'a ScalarBuffer,
+        colormap: Colormap<'a>,
+        /// The values mapped to either end of the colormap. Values outside of
+        /// it are clamped.
+        range: Interval,
+    },
+    Rgba(&'a ColourBuffer),
+}
+
+/// The number of points in each pixel, coloured by a colormap.
+///
+/// Unlike a [`Scatter`], this stays legible with millions of overlapping
+/// points. Counts are scaled logarithmically, and empty pixels are left
+/// transparent.
+#[derive(Debug, Clone, Copy)]
+pub struct Density<'a> {
+    pub data: &'a PointBuffer,
+    pub colormap: Colormap<'a>,
+}
+
+impl crate::gpu::Layer for Density<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+/// A filled region between a series of points and a baseline.
+///
+/// Stacked area charts can be drawn by filling between each cumulative series
+/// and the one below it.
+#[derive(Debug, Clone, Copy)]
+pub struct Area<'a> {
+    pub data: &'a PointBuffer,
+    pub baseline: Baseline<'a>,
+    pub colour: [f32; 4],
+    /// Outlines the data, and the baseline if it is a buffer.
+    pub outline: Option<Outline>,
+}
+
+impl crate::gpu::Layer for Area<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Baseline<'a> {
+    /// A horizontal line at the given y value.
+    Constant(f32),
+    /// Another series, matched to the data point by point.
+    Buffer(&'a PointBuffer),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Outline {
+    pub thickness: f32,
+    pub colour: [f32; 4],
+}
+
+/// A bar for each point, drawn from a baseline to the point.
+///
+/// Grouped bars can be drawn by giving each series in the group a different
+/// `offset`, and stacked bars by using the series below as the baseline.
+#[derive(Debug, Clone, Copy)]
+pub struct Bars<'a> {
+    pub data: &'a PointBuffer,
+    /// The width of each bar in data units.
+    pub width: f32,
+    /// How far to shift each bar along its position axis in data units.
+    pub offset: f32,
+    pub orientation: Orientation,
+    pub baseline: Baseline<'a>,
+    pub colour: [f32; 4],
+}
+
+impl crate::gpu::Layer for Bars<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+/// Price bars showing the open, high, low and close of each candle.
+#[derive(Debug, Clone, Copy)]
+pub struct Candles<'a> {
+    pub data: &'a OhlcBuffer,
+    pub style: CandleStyle,
+    /// The width of each candle in data units.
+    pub width: f32,
+    /// The thickness of the wicks, and of the ticks of OHLC bars, in pixels.
+    pub thickness: f32,
+    /// The colour of candles that close at or above their open.
+    pub up_colour: [f32; 4],
+    /// The colour of candles that close below their open.
+    pub down_colour: [f32; 4],
+}
+
+impl<'a> Candles<'a> {
+    /// Creates green and red candlesticks 0.8 units wide.
+    pub fn new(data: &'a OhlcBuffer) -> Self {
+        Self {
+            data,
+            style: CandleStyle::default(),
+            width: 0.8,
+            thickness: 1.,
+            up_colour: [0.15, 0.65, 0.3, 1.],
+            down_colour: [0.85, 0.2, 0.2, 1.],
+        }
+    }
+}
+
+impl crate::gpu::Layer for Candles<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CandleStyle {
+    /// A box from the open to the close, with a wick from the low to the high.
+    #[default]
+    Candlestick,
+    /// A line from the low to the high, with a tick to the left at the open
+    /// and to the right at the close.
+    Ohlc,
+}
+
+/// A box from the lower to the upper quartile of a distribution, with a line at
+/// the median, whiskers, and a dot for each outlier.
+#[derive(Debug, Clone, Copy)]
+pub struct BoxPlot<'a> {
+    pub data: &'a Distribution,
+    /// Where the box is centred along its position axis, such as the index of
+    /// its category.
+    pub position: f32,
+    /// The width of the box in data units. Whisker caps are half as wide.
+    pub width: f32,
+    pub orientation: Orientation,
+    pub colour: [f32; 4],
+    /// The box outline, median, whiskers and outliers.
+    pub lines: Outline,
+    /// The radius of the outlier dots in pixels.
+    pub outlier_radius: f32,
+}
+
+impl<'a> BoxPlot<'a> {
+    /// Creates a vertical light blue box with black lines.
+    pub fn new(data: &'a Distribution, position: f32) -> Self {
+        Self {
+            data,
+            position,
+            width: 0.5,
+            orientation: Orientation::default(),
+            colour: [0.65, 0.75, 0.9, 1.],
+            lines: Outline {
+                thickness: 1.5,
+                colour: [0., 0., 0., 1.],
+            },
+            outlier_radius: 2.5,
+        }
+    }
+}
+
+impl crate::gpu::Layer for BoxPlot<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.outliers_binding()
+    }
+}
+
+/// The density of a distribution mirrored either side of its position.
+///
+/// A narrow [`BoxPlot`] can be drawn over a violin to show its quartiles.
+#[derive(Debug, Clone, Copy)]
+pub struct Violin<'a> {
+    pub data: &'a Distribution,
+    /// Where the violin is centred along its position axis.
+    pub position: f32,
+    /// The width of the violin at its densest in data units.
+    pub width: f32,
+    pub orientation: Orientation,
+    pub colour: [f32; 4],
+}
+
+impl<'a> Violin<'a> {
+    /// Creates a vertical light blue violin.
+    pub fn new(data: &'a Distribution, position: f32) -> Self {
+        Self {
+            data,
+            position,
+            width: 0.8,
+            orientation: Orientation::default(),
+            colour: [0.65, 0.75, 0.9, 1.],
+        }
+    }
+}
+
+impl crate::gpu::Layer for Violin<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.density_binding()
+    }
+}
+
+/// An arrow from each point showing a vector, such as the wind at that point.
+#[derive(Debug, Clone, Copy)]
+pub struct Quiver<'a> {
+    pub data: &'a PointBuffer,
+    /// The `(u, v)` vector at each point, matched to the points by index.
+    pub vectors: &'a PointBuffer,
+    pub scale: ArrowScale,
+    /// The thickness of the shafts in pixels. Heads grow with it.
+    pub thickness: f32,
+    pub colour: [f32; 4],
+    /// Colours each arrow by the length of its vector instead of using
+    /// `colour`, with `range` mapped to the ends of the colormap.
+    pub colormap: Option<Colormap<'a>>,
+    pub range: Interval,
+}
+
+impl<'a> Quiver<'a> {
+    /// Creates thin black arrows the length of their vectors in data units.
+    pub fn new(data: &'a PointBuffer, vectors: &'a PointBuffer) -> Self {
+        Self {
+            data,
+            vectors,
+            scale: ArrowScale::Data(1.),
+            thickness: 1.5,
+            colour: [0., 0., 0., 1.],
+            colormap: None,
+            range: Interval::UNIT,
+        }
+    }
+}
+
+impl crate::gpu::Layer for Quiver<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+/// How the vectors of a [`Quiver`] are turned into arrows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrowScale {
+    /// Arrows are the vector times this, in data units.
+    Data(f32),
+    /// Arrows point the same way as the vector on screen, and are this many
+    /// pixels long per unit of its length. They keep their size as the plot
+    /// is zoomed.
+    Pixels(f32),
+}
+
+/// A line from a baseline to each point with a marker at its tip, such as for
+/// the samples of a discrete-time signal.
+#[derive(Debug, Clone, Copy)]
+pub struct Stems<'a> {
+    pub data: &'a PointBuffer,
+    pub orientation: Orientation,
+    pub baseline: Baseline<'a>,
+    /// The thickness of the stems in pixels.
+    pub thickness: f32,
+    pub colour: [f32; 4],
+    /// The marker drawn at each point in `colour`.
+    pub marker: Option<Marker>,
+    /// The radius of the markers in pixels.
+    pub radius: f32,
+}
+
+impl<'a> Stems<'a> {
+    /// Creates thin black vertical stems from zero with circles at the tips.
+    pub fn new(data: &'a PointBuffer) -> Self {
+        Self {
+            data,
+            orientation: Orientation::default(),
+            baseline: Baseline::Constant(0.),
+            thickness: 1.,
+            colour: [0., 0., 0., 1.],
+            marker: Some(Marker::default()),
+            radius: 3.,
+        }
+    }
+}
+
+impl crate::gpu::Layer for Stems<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+/// A histogram of one coordinate of each point, binned on the GPU every frame.
+#[derive(Debug, Clone, Copy)]
+pub struct Histogram<'a> {
+    pub data: &'a PointBuffer,
+    /// Which coordinate of each point to bin.
+    pub axis: Axis,
+    /// The range covered by the bins. Values outside of it are ignored, so an
+    /// inverted range draws nothing and an empty one draws no visible bars.
+    pub range: Interval,
+    /// The number of equal bins that `range` is split into.
+    pub bins: NonZeroU32,
+    /// Scales the bars so that the histogram integrates to one.
+    pub density: bool,
+    pub orientation: Orientation,
+    pub colour: [f32; 4],
+}
+
+impl crate::gpu::Layer for Histogram<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+/// The number of points in each of a grid of bins, coloured by a colormap.
+///
+/// Bins are a constant size on screen, so the points are binned again as the
+/// plot is zoomed. Empty bins are left transparent.
+#[derive(Debug, Clone, Copy)]
+pub struct Histogram2d<'a> {
+    pub data: &'a PointBuffer,
+    pub shape: BinShape,
+    /// The width of each bin in pixels. Bins are at least one pixel wide.
+    pub size: f32,
+    pub colormap: Colormap<'a>,
+    /// Scales the counts logarithmically rather than linearly.
+    pub log: bool,
+    /// Shows the count of the bin under the mouse. Counts are read back from
+    /// the GPU, so the tooltip catches up with the mouse a frame or two late.
+    pub tooltip: Option<Tooltip<'a>>,
+}
+
+impl crate::gpu::Layer for Histogram2d<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+/// A box of text next to the mouse.
+#[derive(Debug, Clone, Copy)]
+pub struct Tooltip<'a> {
+    pub font: &'a Font,
+    /// The font size in pixels.
+    pub size: f32,
+    pub colour: [f32; 4],
+    pub background: [f32; 4],
+}
+
+impl<'a> Tooltip<'a> {
+    /// Creates black text on a translucent white box.
+    pub fn new(font: &'a Font) -> Self {
+        Self {
+            font,
+            size: 12.,
+            colour: [0., 0., 0., 1.],
+            background: [1., 1., 1., 0.9],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinShape {
+    /// Square bins.
+    #[default]
+    Rectangle,
+    /// Hexagonal bins with a point at the top, like matplotlib's `hexbin`.
+    Hexagon,
+}
+
+/// Whiskers showing the uncertainty in each point.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorBars<'a> {
+    pub data: &'a PointBuffer,
+    pub errors: &'a ErrorBuffer,
+    pub thickness: f32,
+    /// The length of the caps across the end of each whisker in pixels, or zero
+    /// for no caps.
+    pub cap_width: f32,
+    pub colour: [f32; 4],
+}
+
+impl crate::gpu::Layer for ErrorBars<'_> {
+    const HAS_DATA: bool = true;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.data.as_entire_binding()
+    }
+}
+
+/// An [`Image`] stretched over a rectangle in data coordinates, with each
+/// value coloured by a colormap.
+#[derive(Debug, Clone, Copy)]
+pub struct Heatmap<'a> {
+    pub image: &'a Image,
+    /// Where the edges of the image are in data coordinates.
+    pub bounds: Bounds,
+    pub colormap: Colormap<'a>,
+    /// The values mapped to either end of the colormap. Values outside of it
+    /// are clamped.
+    pub range: Interval,
+    pub nan_colour: [f32; 4],
+}
+
+impl crate::gpu::Layer for Heatmap<'_> {
+    const HAS_DATA: bool = false;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        panic!("heatmap layer does not have a buffer")
+    }
+}
+
+/// Contour lines and filled bands through the values of an [`Image`].
+///
+/// Each value is placed at the centre of its cell, so contours line up with a
+/// [`Heatmap`] of the same image and bounds.
+#[derive(Debug, Clone, Copy)]
+pub struct Contour<'a> {
+    pub image: &'a Image,
+    /// Where the edges of the image are in data coordinates.
+    pub bounds: Bounds,
+    /// The values to draw contours at, in increasing order. Only the first
+    /// sixteen levels are drawn.
+    pub levels: &'a [f32],
+    /// Draws a line along each level.
+    pub lines: Option<Outline>,
+    /// Fills the band between each pair of consecutive levels with colours
+    /// evenly spaced along a colormap.
+    pub fill: Option<Colormap<'a>>,
+    /// Labels each level with its value along the lines.
+    pub labels: Option<ContourLabels<'a>>,
+}
+
+/// The most levels drawn by a [`Contour`].
+pub(crate) const MAX_CONTOUR_LEVELS: usize = 16;
+
+impl Contour<'_> {
+    /// The levels that are drawn, which are the first sixteen.
+    pub(crate) fn drawn_levels(&self) -> &[f32] {
+        &self.levels[..self.levels.len().min(MAX_CONTOUR_LEVELS)]
+    }
+}
+
+impl crate::gpu::Layer for Contour<'_> {
+    const HAS_DATA: bool = false;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        panic!("contour layer does not have a buffer")
+    }
+}
+
+/// The values of contour levels, written along the contours and turned to
+/// follow them.
+#[derive(Debug, Clone, Copy)]
+pub struct ContourLabels<'a> {
+    pub font: &'a Font,
+    /// The font size in pixels.
+    pub size: f32,
+    pub colour: [f32; 4],
+    /// Fills a box behind each label, hiding the line under it.
+    pub background: Option<[f32; 4]>,
+    /// The least distance between labels in pixels.
+    pub spacing: f32,
+}
+
+impl<'a> ContourLabels<'a> {
+    /// Creates black labels at least 150 pixels apart.
+    pub fn new(font: &'a Font) -> Self {
+        Self {
+            font,
+            size: 12.,
+            colour: [0., 0., 0., 1.],
+            background: None,
+            spacing: 150.,
+        }
+    }
+}
+
+/// The columns of a [`WaterfallBuffer`] laid out side by side along a time
+/// axis, with each value coloured by a colormap.
+///
+/// The newest column ends at `start + step * data.pushed()`, so the waterfall
+/// scrolls along the time axis as columns are pushed.
+#[derive(Debug, Clone, Copy)]
+pub struct Waterfall<'a> {
+    pub data: &'a WaterfallBuffer,
+    /// The axis that columns are laid out along.
+    pub time_axis: Axis,
+    /// The time at which the first column pushed starts.
+    pub start: f64,
+    /// The time covered by each column.
+    pub step: f64,
+    /// Where the edges of the first and last bins are along the other axis.
+    pub bins: Interval,
+    pub colormap: Colormap<'a>,
+    /// The values mapped to either end of the colormap. Values outside of it
+    /// are clamped.
+    pub range: Interval,
+    pub nan_colour: [f32; 4],
+}
+
+impl crate::gpu::Layer for Waterfall<'_> {
+    const HAS_DATA: bool = false;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        panic!("waterfall layer does not have a buffer")
+    }
+}
+
+/// The direction bars extend in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    #[default]
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// Which of the two y axes is meant, where the right one is the secondary
+/// axis set in the layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Side {
+    #[default]
+    Left,
+    Right,
+}
+
+/// A line or shaded span across the whole viewport, such as a threshold or a
+/// time window.
+#[derive(Debug, Clone, Copy)]
+pub struct Reference {
+    /// The axis that `range` is on, so references on the x axis are vertical.
+    /// In polar plots they are rays, and references on the y axis are circles.
+    pub axis: Axis,
+    pub range: Interval,
+    /// How far the reference extends past `range` in pixels.
+    pub thickness: f32,
+    pub colour: [f32; 4],
+}
+
+impl Reference {
+    /// Creates a line at `value`.
+    pub fn line(axis: Axis, value: f64, thickness: f32, colour: [f32; 4]) -> Self {
+        Self {
+            axis,
+            range: Interval {
+                min: value,
+                max: value,
+            },
+            thickness,
+            colour,
+        }
+    }
+
+    /// Creates a span shading `range`.
+    pub fn span(axis: Axis, range: Interval, colour: [f32; 4]) -> Self {
+        Self {
+            axis,
+            range,
+            thickness: 0.,
+            colour,
+        }
+    }
+}
+
+impl crate::gpu::Layer for Reference {
+    const HAS_DATA: bool = false;
+
+    fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        panic!("reference layer does not have a buffer")
+    }
+}
+
+/// Text, and optionally an arrow, pointing at a position in data coordinates.
+///
+/// Annotations are drawn with vello on top of the layers before them.
+#[derive(Debug, Clone, Copy)]
+pub struct Annotation<'a> {
+    pub text: &'a str,
+    pub font: &'a Font,
+    /// The font size in pixels.
+    pub size: f32,
+    pub colour: [f32; 4],
+    /// The position in data coordinates that the annotation points at.
+    pub position: [f64; 2],
+    /// Where the text is placed relative to `position`, in pixels with y up.
+    pub offset: [f32; 2],
+    /// How the text is aligned horizontally and vertically around its
+    /// placement.
+    pub align: (HorizontalAlign, VerticalAlign),
+    /// The angle of the text in degrees anticlockwise.
+    pub rotation: f32,
+    /// Fills a box behind the text.
+    pub background: Option<[f32; 4]>,
+    /// Draws an arrow from the text to `position`.
+    pub arrow: Option<Outline>,
+}
+
+impl<'a> Annotation<'a> {
+    /// Creates black text centred on `position`.
+    pub fn new(text: &'a str, font: &'a Font, position: [f64; 2]) -> Self {
+        Self {
+            text,
+            font,
+            size: 14.,
+            colour: [0., 0., 0., 1.],
+            position,
+            offset: [0., 0.],
+            align: (HorizontalAlign::default(), VerticalAlign::default()),
+            rotation: 0.,
+            background: None,
+            arrow: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HorizontalAlign {
+    Left,
+    #[default]
+    Centre,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlign {
+    Top,
+    #[default]
+    Middle,
+    Bottom,
+}
+
+/// A filled or outlined shape in data coordinates, such as a region of
+/// interest.
+///
+/// Shapes are drawn with vello on top of the layers before them.
+#[derive(Debug, Clone, Copy)]
+pub struct Shape<'a> {
+    pub geometry: Geometry<'a>,
+    pub fill: Option<[f32; 4]>,
+    /// The outline, with its thickness in pixels.
+    pub outline: Option<Outline>,
+}
+
+/// In polar plots geometry is in (theta, r), so a rectangle is a sector of an
+/// annulus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Geometry<'a> {
+    /// A closed polygon through the given vertices.
+    Polygon(&'a [[f64; 2]]),
+    Rectangle(Bounds),
+    /// A circle in data units, which is an ellipse on screen unless the axes
+    /// have the same scale.
+    Circle {
+        centre: [f64; 2],
+        radius: f64,
+    },
+}
+
+/// A key naming each labelled [`Line`] and [`Scatter`] in the plot.
+///
+/// Lines and scatters with the same label share an entry. Clicking an entry
+/// hides or shows its series.
+#[derive(Debug, Clone, Copy)]
+pub struct Legend<'a> {
+    pub font: &'a Font,
+    /// The font size in pixels.
+    pub size: f32,
+    pub colour: [f32; 4],
+    pub background: Option<[f32; 4]>,
+    pub placement: LegendPlacement,
+}
+
+impl<'a> Legend<'a> {
+    /// Creates a legend in the top right corner on a translucent white box.
+    pub fn new(font: &'a Font) -> Self {
+        Self {
+            font,
+            size: 14.,
+            colour: [0., 0., 0., 1.],
+            background: Some([1., 1., 1., 0.8]),
+            placement: LegendPlacement::default(),
+        }
+    }
+}
+
+/// Where a [`Legend`] is placed relative to the area inside the padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LegendPlacement {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    /// To the right of the plot, in the right padding.
+    OutsideRight,
+    /// The inside corner covering the fewest points.
+    ///
+    /// Points are counted on the GPU, so the legend moves a frame after the
+    /// data does.
+    Best,
+}
+
+/// Ticks and labels naming the categories along an axis, which has a
+/// [`Scale::Categorical`] scale in the layout.
+///
+/// Category `i` is at `i` in data coordinates, and its label is drawn just
+/// outside the area inside the padding. Crowded labels are rotated on the x
+/// axis and then thinned out to every few categories. Categorical axes are
+/// not drawn in polar plots.
+#[derive(Debug, Clone, Copy)]
+pub struct CategoricalAxis<'a> {
+    pub axis: Axis,
+    pub categories: &'a [&'a str],
+    pub font: &'a Font,
+    /// The font size in pixels.
+    pub size: f32,
+    pub colour: [f32; 4],
+    /// The widest a label can be in pixels before it is truncated with an
+    /// ellipsis.
+    pub max_label_width: f32,
+    pub tick_length: f32,
+    /// Whether labels on the x axis are rotated rather than thinned out when
+    /// they would overlap.
+    pub rotate_labels: bool,
+}
+
+impl<'a> CategoricalAxis<'a> {
+    /// Creates black labels with short ticks.
+    pub fn new(axis: Axis, categories: &'a [&'a str], font: &'a Font) -> Self {
+        Self {
+            axis,
+            categories,
+            font,
+            size: 14.,
+            colour: [0., 0., 0., 1.],
+            max_label_width: 120.,
+            tick_length: 5.,
+            rotate_labels: true,
+        }
+    }
+
+    /// The scale to use for this axis in the layout, if there are any
+    /// categories.
+    pub fn scale(&self) -> Option<Scale> {
+        NonZeroUsize::new(self.categories.len()).map(|count| Scale::Categorical { count })
+    }
+
+    /// The position of the category called `name` in data coordinates.
+    pub fn position(&self, name: &str) -> Option<f64> {
+        self.categories
+            .iter()
+            .position(|category| *category == name)
+            .map(|i| i as f64)
+    }
+}
+
+/// Numbered ticks at multiples of `spacing` along an axis.
+///
+/// Labels are drawn just outside the area inside the padding, below the plot
+/// for the x axis and on `side` for the y axes.
+///
+/// In polar plots, x ticks mark theta in degrees around the largest circle
+/// inside the padding, and y ticks mark r along the ray at theta = 0.
+#[derive(Debug, Clone, Copy)]
+pub struct Ticks<'a> {
+    pub axis: Axis,
+    /// The y axis that the ticks are for, which is ignored for the x axis.
+    pub side: Side,
+    pub spacing: f64,
+    pub font: &'a Font,
+    /// The font size in pixels.
+    pub size: f32,
+    pub colour: [f32; 4],
+    pub tick_length: f32,
+}
+
+impl<'a> Ticks<'a> {
+    /// Creates black labels with short ticks, on the left for the y axis.
+    pub fn new(axis: Axis, spacing: f64, font: &'a Font) -> Self {
+        Self {
+            axis,
+            side: Side::Left,
+            spacing,
+            font,
+            size: 14.,
+            colour: [0., 0., 0., 1.],
+            tick_length: 5.,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Grid {
     // TODO: this uses pixel units instead of -1 1 units.
+    /// In polar plots this is (theta, r), giving rays and circles.
     pub spacing: [f32; 2],
     pub thickness: f32,
     pub axis_thickness: f32,
@@ -58,3 +1094,41 @@ impl crate::gpu::Layer for Grid {
         panic!("grid layer does not have a buffer")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_patterns_make_room_for_caps() {
+        assert_eq!(Dash::Solid.pattern(LineCap::Round), []);
+        assert_eq!(Dash::Dashed.pattern(LineCap::Butt), [3.7, 1.6]);
+        assert_eq!(Dash::Dashed.pattern(LineCap::Round), [3.7 - 1., 1.6 + 1.]);
+        // Dots shorter than the cap are drawn as the cap alone.
+        assert_eq!(Dash::Dotted.pattern(LineCap::Square), [0., 1.65 + 1.]);
+    }
+
+    #[test]
+    fn custom_patterns_are_repeated_to_an_even_length() {
+        let pattern = DashPattern::new(&[5., 2., 1.]).unwrap();
+        assert_eq!(
+            Dash::Custom(pattern).pattern(LineCap::Round),
+            [5., 2., 1., 5., 2., 1.]
+        );
+        let pattern = DashPattern::new(&[3., 1.]).unwrap();
+        assert_eq!(Dash::Custom(pattern).pattern(LineCap::Butt), [3., 1.]);
+    }
+
+    #[test]
+    fn dash_patterns_reject_invalid_lengths() {
+        assert_eq!(DashPattern::new(&[]), None);
+        assert_eq!(DashPattern::new(&[1., 0.]), None);
+        assert_eq!(DashPattern::new(&[1., -2.]), None);
+        assert_eq!(DashPattern::new(&[1., f32::INFINITY]), None);
+        assert_eq!(DashPattern::new(&[1., f32::NAN]), None);
+        // Five lengths become ten after repeating.
+        assert_eq!(DashPattern::new(&[1.; 5]), None);
+        assert_eq!(DashPattern::new(&[1.; 9]), None);
+        assert!(DashPattern::new(&[1.; 8]).is_some());
+    }
+}
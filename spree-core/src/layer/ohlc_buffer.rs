@@ -0,0 +1,89 @@
+use vello::wgpu::{self, CommandBuffer};
+
+use crate::gpu::GpuBuffer;
+
+const FIELDS: usize = 5;
+
+/// The prices over one period of time, drawn by [`Candles`](super::Candles).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Candle {
+    /// Where the candle is centred on the x axis.
+    pub time: f32,
+    pub open: f32,
+    pub high: f32,
+    pub low: f32,
+    pub close: f32,
+}
+
+impl Candle {
+    fn fields(self) -> [f32; FIELDS] {
+        [self.time, self.open, self.high, self.low, self.close]
+    }
+}
+
+/// Candles stored on the GPU, which can be appended to and have their last
+/// candle updated as prices change.
+#[derive(Debug)]
+pub struct OhlcBuffer {
+    inner: GpuBuffer<f32>,
+}
+
+impl OhlcBuffer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            inner: GpuBuffer::new(
+                device,
+                wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::STORAGE,
+                0,
+                |_| {},
+            ),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len() / FIELDS
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[must_use]
+    pub fn append(&mut self, candle: Candle, device: &wgpu::Device) -> CommandBuffer {
+        self.extend(&[candle], device)
+    }
+
+    #[must_use]
+    pub fn extend(&mut self, candles: &[Candle], device: &wgpu::Device) -> CommandBuffer {
+        self.inner.extend(device, candles.len() * FIELDS, |buffer| {
+            fill(buffer, candles)
+        })
+    }
+
+    /// Replaces the last candle, such as when a new price arrives before its
+    /// period ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is empty.
+    #[must_use]
+    pub fn update_last(&mut self, candle: Candle, device: &wgpu::Device) -> CommandBuffer {
+        assert!(!self.is_empty(), "there is no candle to update");
+
+        let start = (self.len() - 1) * FIELDS;
+        self.inner
+            .write(device, start, FIELDS, |buffer| fill(buffer, &[candle]))
+    }
+
+    pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.inner.as_entire_binding()
+    }
+}
+
+fn fill(buffer: &mut [f32], candles: &[Candle]) {
+    for (values, candle) in buffer.chunks_exact_mut(FIELDS).zip(candles) {
+        values.copy_from_slice(&candle.fields());
+    }
+}
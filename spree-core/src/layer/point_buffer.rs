@@ -35,6 +35,16 @@ impl PointBuffer {
         self.extend(&[x], &[y], device)
     }
 
+    /// Appends a gap, splitting any line drawn from this buffer in two.
+    ///
+    /// Points with a NaN coordinate are treated as gaps, so `append(f32::NAN,
+    /// f32::NAN, device)` and NaNs passed to [`extend`](Self::extend) are
+    /// equivalent.
+    #[must_use]
+    pub fn append_gap(&mut self, device: &wgpu::Device) -> CommandBuffer {
+        self.append(f32::NAN, f32::NAN, device)
+    }
+
     #[must_use]
     pub fn extend(&mut self, xs: &[f32], ys: &[f32], device: &wgpu::Device) -> CommandBuffer {
         assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
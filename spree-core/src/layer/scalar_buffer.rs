@@ -0,0 +1,49 @@
+use vello::wgpu::{self, CommandBuffer};
+
+use crate::gpu::GpuBuffer;
+
+/// A value for each point of a [`PointBuffer`](super::PointBuffer), matched to
+/// the points by index.
+#[derive(Debug)]
+pub struct ScalarBuffer {
+    inner: GpuBuffer<f32>,
+}
+
+impl ScalarBuffer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            inner: GpuBuffer::new(
+                device,
+                wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::STORAGE,
+                0,
+                |_| {},
+            ),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[must_use]
+    pub fn append(&mut self, value: f32, device: &wgpu::Device) -> CommandBuffer {
+        self.extend(&[value], device)
+    }
+
+    #[must_use]
+    pub fn extend(&mut self, values: &[f32], device: &wgpu::Device) -> CommandBuffer {
+        self.inner.extend(device, values.len(), |buffer| {
+            buffer.copy_from_slice(values)
+        })
+    }
+
+    pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.inner.as_entire_binding()
+    }
+}
@@ -0,0 +1,116 @@
+use vello::wgpu::{self, CommandBuffer};
+
+use super::Image;
+
+/// A fixed number of the most recent columns of a streaming signal, such as
+/// the spectra of a spectrogram, drawn by a [`Waterfall`](super::Waterfall).
+///
+/// Columns are stored in a ring on the GPU, so pushing a column only uploads
+/// that column and overwrites the oldest one once the buffer is full.
+#[derive(Debug)]
+pub struct WaterfallBuffer {
+    image: Image,
+    pushed: u64,
+}
+
+impl WaterfallBuffer {
+    /// Creates a buffer holding up to `columns` columns of `bins` values each.
+    pub fn new(columns: u32, bins: u32, device: &wgpu::Device) -> Self {
+        Self {
+            image: Image::new(bins, columns, device),
+            pushed: 0,
+        }
+    }
+
+    /// The number of columns that will be drawn.
+    pub fn len(&self) -> usize {
+        drawn(self.pushed, self.capacity()) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pushed == 0
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.image.height()
+    }
+
+    pub fn bins(&self) -> u32 {
+        self.image.width()
+    }
+
+    /// The total number of columns pushed, including those overwritten.
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
+
+    #[must_use]
+    pub fn push(&mut self, column: &[f32], device: &wgpu::Device) -> CommandBuffer {
+        assert_eq!(
+            column.len(),
+            self.bins() as usize,
+            "columns must have one value per bin"
+        );
+
+        let row = ring_position(self.pushed, self.capacity());
+        self.pushed += 1;
+        self.image.write_rows(column, row, device)
+    }
+
+    /// The ring position of the oldest column that will be drawn.
+    pub(crate) fn oldest(&self) -> u32 {
+        ring_position(self.pushed - self.len() as u64, self.capacity())
+    }
+
+    pub(crate) fn as_binding(&self) -> wgpu::BindingResource<'_> {
+        self.image.as_binding()
+    }
+}
+
+/// The number of columns drawn after `pushed` columns were pushed.
+fn drawn(pushed: u64, capacity: u32) -> u64 {
+    pushed.min(u64::from(capacity))
+}
+
+/// The row of the ring that holds the column pushed after `index` others.
+fn ring_position(index: u64, capacity: u32) -> u32 {
+    (index % u64::from(capacity)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The rows of the ring from the oldest column drawn to the newest, as the
+    /// shader reads them.
+    fn rows(pushed: u64, capacity: u32) -> Vec<u32> {
+        let drawn = drawn(pushed, capacity);
+        let oldest = ring_position(pushed - drawn, capacity);
+        (0..drawn as u32)
+            .map(|column| (oldest + column) % capacity)
+            .collect()
+    }
+
+    #[test]
+    fn columns_fill_the_ring_in_order() {
+        assert_eq!(rows(0, 4), []);
+        assert_eq!(rows(3, 4), [0, 1, 2]);
+        assert_eq!(ring_position(3, 4), 3);
+    }
+
+    #[test]
+    fn full_rings_overwrite_the_oldest_column() {
+        assert_eq!(rows(4, 4), [0, 1, 2, 3]);
+        assert_eq!(rows(6, 4), [2, 3, 0, 1]);
+        assert_eq!(rows(8, 4), [0, 1, 2, 3]);
+        // The next push replaces the oldest column drawn.
+        assert_eq!(ring_position(6, 4), rows(6, 4)[0]);
+    }
+
+    #[test]
+    fn the_newest_column_is_the_last_one_written() {
+        for pushed in 1..20 {
+            assert_eq!(rows(pushed, 5).last(), Some(&ring_position(pushed - 1, 5)));
+        }
+    }
+}
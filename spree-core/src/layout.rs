@@ -1,8 +1,12 @@
-use std::ops::{Add, AddAssign};
+use std::{
+    f64::consts::PI,
+    num::NonZeroUsize,
+    ops::{Add, AddAssign},
+};
 
 use winit::window::Window;
 
-use crate::gpu::SceneParams;
+use crate::{gpu::SceneParams, layer::Side};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PlotLayout {
@@ -11,6 +15,12 @@ pub struct PlotLayout {
     pub padding: Padding,
     pub initial_bounds: Option<Bounds>,
     pub interaction_bounds: Bounds,
+    pub projection: Projection,
+    pub x_scale: Scale,
+    pub y_scale: Scale,
+    /// The initial bounds of the y axis on the right, if there is one.
+    pub secondary_y: Option<Interval>,
+    pub secondary_y_scale: Scale,
 }
 
 impl PlotLayout {
@@ -43,6 +53,31 @@ impl PlotLayout {
         self
     }
 
+    pub fn with_projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub fn with_x_scale(mut self, scale: Scale) -> Self {
+        self.x_scale = scale;
+        self
+    }
+
+    pub fn with_y_scale(mut self, scale: Scale) -> Self {
+        self.y_scale = scale;
+        self
+    }
+
+    pub fn with_secondary_y(mut self, bounds: Interval) -> Self {
+        self.secondary_y = Some(bounds);
+        self
+    }
+
+    pub fn with_secondary_y_scale(mut self, scale: Scale) -> Self {
+        self.secondary_y_scale = scale;
+        self
+    }
+
     pub(crate) fn instantiate(self, window: &Window) -> PlotInstanceLayout {
         PlotInstanceLayout {
             logical_width: self.width,
@@ -50,6 +85,11 @@ impl PlotLayout {
             padding: self.padding,
             data_bounds: self.initial_bounds.unwrap_or(Bounds::UNIT),
             interaction_bounds: self.interaction_bounds,
+            projection: self.projection,
+            x_scale: self.x_scale,
+            y_scale: self.y_scale,
+            secondary_y: self.secondary_y,
+            secondary_y_scale: self.secondary_y_scale,
             scale_factor: window.scale_factor(),
         }
     }
@@ -74,6 +114,67 @@ impl Default for PlotLayout {
                 y: Interval { min: -5.0, max: 5. },
             }),
             interaction_bounds: Bounds::INFINITY,
+            projection: Projection::Cartesian,
+            x_scale: Scale::Linear,
+            y_scale: Scale::Linear,
+            secondary_y: None,
+            secondary_y_scale: Scale::Linear,
+        }
+    }
+}
+
+/// How data coordinates are placed in the plot.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Projection {
+    #[default]
+    Cartesian,
+    /// Points are (theta, r) with theta in radians, around the centre of the
+    /// plot. Only the y bounds are used, with `y.max` being the radius that
+    /// reaches the nearest edge.
+    ///
+    /// Zooming scales the radius and dragging rotates the plot.
+    /// Heatmaps, waterfalls, contours and 2D histograms are not drawn, as
+    /// their grids of cells only make sense on Cartesian axes.
+    Polar {
+        /// The angle of theta = 0 in radians, anticlockwise from the right.
+        rotation: f64,
+        /// Whether theta increases clockwise, as on a compass.
+        clockwise: bool,
+    },
+}
+
+/// How values are spread along an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scale {
+    #[default]
+    Linear,
+    /// Named categories at the integers from zero, as labelled by a
+    /// [`CategoricalAxis`](crate::layer::CategoricalAxis).
+    ///
+    /// Panning and zooming stay within the categories, and panning finishes
+    /// with the view snapped to the edges of a category.
+    Categorical { count: NonZeroUsize },
+}
+
+impl Scale {
+    /// The interval that the view is kept within.
+    fn extent(self) -> Interval {
+        match self {
+            Scale::Linear => Interval::INFINITY,
+            Scale::Categorical { count } => Interval {
+                min: -0.5,
+                max: count.get() as f64 - 0.5,
+            },
+        }
+    }
+
+    /// Moves `interval` so that it starts at the edge of a category.
+    fn snap(self, interval: Interval) -> Interval {
+        match self {
+            Scale::Linear => interval,
+            Scale::Categorical { .. } => {
+                interval + ((interval.min + 0.5).round() - 0.5 - interval.min)
+            }
         }
     }
 }
@@ -86,6 +187,11 @@ pub struct PlotInstanceLayout {
 
     pub(crate) data_bounds: Bounds,
     pub(crate) interaction_bounds: Bounds,
+    pub(crate) projection: Projection,
+    pub(crate) x_scale: Scale,
+    pub(crate) y_scale: Scale,
+    pub(crate) secondary_y: Option<Interval>,
+    pub(crate) secondary_y_scale: Scale,
 
     pub(crate) scale_factor: f64,
 }
@@ -95,9 +201,36 @@ impl PlotInstanceLayout {
         self.data_bounds
     }
 
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    /// The bounds of the y axis on the right, which pans and zooms along with
+    /// the primary y axis.
+    pub fn secondary_y(&self) -> Option<Interval> {
+        self.secondary_y
+    }
+
     pub(crate) fn scene_params(&self) -> SceneParams {
+        self.scene_params_for(Side::Left)
+    }
+
+    /// The scene parameters for data plotted against the y axis on `side`,
+    /// falling back to the primary axis if there is no secondary axis.
+    pub(crate) fn scene_params_for(&self, side: Side) -> SceneParams {
+        if let Projection::Polar {
+            rotation,
+            clockwise,
+        } = self.projection
+        {
+            return self.polar_scene_params(rotation, clockwise);
+        }
+
         let x = self.data_bounds.x;
-        let y = self.data_bounds.y;
+        let y = match side {
+            Side::Left => self.data_bounds.y,
+            Side::Right => self.secondary_y.unwrap_or(self.data_bounds.y),
+        };
 
         let width = x.size() as f32;
         let height = y.size() as f32;
@@ -130,7 +263,36 @@ impl PlotInstanceLayout {
             projection_matrix,
             inverse_projection_matrix,
             viewport_size: [self.logical_width as f32, self.logical_height as f32],
-            _padding: [0., 0.],
+            polar: 0.,
+            rotation: 0.,
+        }
+    }
+
+    /// Maps the plane that polar points are placed on so that a radius of
+    /// `y.max` reaches the nearest edge, with the same scale on both axes.
+    fn polar_scene_params(&self, rotation: f64, clockwise: bool) -> SceneParams {
+        let radius = self.data_bounds.y.max;
+        let size = self.logical_width.min(self.logical_height);
+
+        let x_scale = (size / (self.logical_width * radius)) as f32;
+        let y_scale = (size / (self.logical_height * radius)) as f32;
+
+        SceneParams {
+            projection_matrix: [
+                [x_scale, 0., 0., 0.],
+                [0., y_scale, 0., 0.],
+                [0., 0., 1., 0.],
+                [0., 0., 0., 1.],
+            ],
+            inverse_projection_matrix: [
+                [1. / x_scale, 0., 0., 0.],
+                [0., 1. / y_scale, 0., 0.],
+                [0., 0., 1., 0.],
+                [0., 0., 0., 1.],
+            ],
+            viewport_size: [self.logical_width as f32, self.logical_height as f32],
+            polar: if clockwise { -1. } else { 1. },
+            rotation: rotation as f32,
         }
     }
 
@@ -200,6 +362,17 @@ impl PlotInstanceLayout {
             return;
         }
 
+        if let Projection::Polar { rotation, .. } = &mut self.projection {
+            // Rotate by the angle swept around the centre, with y up.
+            let centre = (
+                self.logical_width * self.scale_factor / 2.,
+                self.logical_height * self.scale_factor / 2.,
+            );
+            let angle = |position: (f64, f64)| (centre.1 - position.1).atan2(position.0 - centre.0);
+            *rotation = (*rotation + angle(current_position) - angle(pre_position)) % (2. * PI);
+            return;
+        }
+
         let change = (
             current_position.0 - pre_position.0,
             current_position.1 - pre_position.1,
@@ -210,16 +383,37 @@ impl PlotInstanceLayout {
         let data_y =
             change.1 * self.data_bounds.y.size() / (self.scale_factor * self.inner_height());
 
+        let prior_y = self.data_bounds.y;
         self.data_bounds.x += data_x;
         self.data_bounds.y += data_y;
 
         self.data_bounds = self.interaction_bounds.clamp(self.data_bounds);
+        self.bound_to_scales();
+
+        // Follow how far the primary axis actually moved, so the two axes
+        // stay aligned when it is stopped at a bound.
+        if let Some(secondary_y) = &mut self.secondary_y {
+            let moved = (self.data_bounds.y.min - prior_y.min) / prior_y.size();
+            *secondary_y = self
+                .secondary_y_scale
+                .extent()
+                .bound(*secondary_y + moved * secondary_y.size());
+        }
         println!("{:?}", self.data_bounds.x);
         //println!("{:?} {:?}", start_drag_mouse_position, current_position);
     }
 
     pub fn zoom(&mut self, mouse_position: (f64, f64), factor: f64) {
+        if let Projection::Polar { .. } = self.projection {
+            // Zoom radially, keeping the centre in place.
+            let radius = self.data_bounds.y.max * factor;
+            self.data_bounds.y.max = radius.min(self.interaction_bounds.y.max);
+            return;
+        }
+
         if let Some(data_position) = self.convert_to_data_position(mouse_position) {
+            let prior_y = self.data_bounds.y;
+            let prior_secondary_y = self.secondary_y;
             self.data_bounds = Bounds {
                 x: Interval {
                     min: data_position.0 - (data_position.0 - self.data_bounds.x.min) * factor,
@@ -232,8 +426,39 @@ impl PlotInstanceLayout {
             };
 
             self.data_bounds = self.interaction_bounds.bound(self.data_bounds);
+            self.bound_to_scales();
+
+            // Cover the same part of the secondary axis as the primary axis
+            // ended up covering, so the two stay aligned when it is bounded.
+            if let Some(secondary_y) = prior_secondary_y {
+                let follow = |y: f64| {
+                    secondary_y.min + (y - prior_y.min) * secondary_y.size() / prior_y.size()
+                };
+                self.secondary_y = Some(self.secondary_y_scale.extent().bound(Interval {
+                    min: follow(self.data_bounds.y.min),
+                    max: follow(self.data_bounds.y.max),
+                }));
+            }
         }
     }
+
+    /// Snaps categorical axes to whole categories once a drag has finished.
+    pub fn end_drag(&mut self) {
+        self.data_bounds.x = self.x_scale.snap(self.data_bounds.x);
+        self.data_bounds.y = self.y_scale.snap(self.data_bounds.y);
+        self.secondary_y = self
+            .secondary_y
+            .map(|secondary_y| self.secondary_y_scale.snap(secondary_y));
+        self.bound_to_scales();
+    }
+
+    fn bound_to_scales(&mut self) {
+        self.data_bounds.x = self.x_scale.extent().bound(self.data_bounds.x);
+        self.data_bounds.y = self.y_scale.extent().bound(self.data_bounds.y);
+        self.secondary_y = self
+            .secondary_y
+            .map(|secondary_y| self.secondary_y_scale.extent().bound(secondary_y));
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -305,12 +530,12 @@ impl Interval {
             let shift = self.min - other.min;
             Self {
                 min: self.min,
-                max: self.max + shift,
+                max: other.max + shift,
             }
         } else if other.max > self.max {
             let shift = other.max - self.max;
             Self {
-                min: self.min - shift,
+                min: other.min - shift,
                 max: self.max,
             }
         } else {
@@ -362,3 +587,127 @@ pub struct Padding {
     pub left: f64,
     pub right: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIVE: Scale = Scale::Categorical {
+        count: NonZeroUsize::new(5).unwrap(),
+    };
+
+    fn interval(min: f64, max: f64) -> Interval {
+        Interval { min, max }
+    }
+
+    /// A 300 by 300 pixel plot showing `x` along a categorical x axis.
+    fn categorical_layout(x: Interval) -> PlotInstanceLayout {
+        PlotInstanceLayout {
+            logical_width: 300.,
+            logical_height: 300.,
+            padding: Padding {
+                top: 0.,
+                bottom: 0.,
+                left: 0.,
+                right: 0.,
+            },
+            data_bounds: Bounds {
+                x,
+                y: Interval::UNIT,
+            },
+            interaction_bounds: Bounds::INFINITY,
+            projection: Projection::Cartesian,
+            x_scale: FIVE,
+            y_scale: Scale::Linear,
+            secondary_y: None,
+            secondary_y_scale: Scale::Linear,
+            scale_factor: 1.,
+        }
+    }
+
+    #[test]
+    fn extent_covers_every_category() {
+        assert_eq!(FIVE.extent(), interval(-0.5, 4.5));
+        assert_eq!(Scale::Linear.extent(), Interval::INFINITY);
+    }
+
+    #[test]
+    fn snap_moves_to_the_nearest_category_edge() {
+        assert_eq!(FIVE.snap(interval(-0.25, 2.75)), interval(-0.5, 2.5));
+        assert_eq!(FIVE.snap(interval(0.75, 2.75)), interval(0.5, 2.5));
+        assert_eq!(FIVE.snap(interval(1.25, 3.25)), interval(1.5, 3.5));
+        assert_eq!(
+            Scale::Linear.snap(interval(0.25, 1.25)),
+            interval(0.25, 1.25)
+        );
+    }
+
+    #[test]
+    fn bound_shifts_into_range() {
+        let range = interval(-0.5, 4.5);
+        assert_eq!(range.bound(interval(-1., 2.)), interval(-0.5, 2.5));
+        assert_eq!(range.bound(interval(3., 6.)), interval(1.5, 4.5));
+        assert_eq!(range.bound(interval(0., 3.)), interval(0., 3.));
+        assert_eq!(range.bound(interval(-1., 6.)), range);
+    }
+
+    #[test]
+    fn panning_stops_at_the_first_category() {
+        let mut layout = categorical_layout(interval(-0.5, 2.5));
+        for _ in 0..3 {
+            layout.drag((150., 150.), (150., 150.), (250., 150.));
+        }
+        layout.end_drag();
+        assert_eq!(layout.data_bounds.x, interval(-0.5, 2.5));
+    }
+
+    #[test]
+    fn secondary_axis_pans_with_the_primary_axis() {
+        let mut layout = PlotInstanceLayout {
+            data_bounds: Bounds {
+                x: interval(0., 1.),
+                y: interval(-0.5, 2.5),
+            },
+            y_scale: FIVE,
+            secondary_y: Some(interval(0., 300.)),
+            ..categorical_layout(interval(0., 1.))
+        };
+
+        // The primary axis is already at its first category.
+        layout.drag((150., 150.), (150., 150.), (150., 50.));
+        assert_eq!(layout.data_bounds.y, interval(-0.5, 2.5));
+        assert_eq!(layout.secondary_y, Some(interval(0., 300.)));
+
+        layout.drag((150., 150.), (150., 150.), (150., 250.));
+        assert_eq!(layout.data_bounds.y, interval(0.5, 3.5));
+        assert_eq!(layout.secondary_y, Some(interval(100., 400.)));
+    }
+
+    #[test]
+    fn secondary_axis_zooms_with_the_primary_axis() {
+        let mut layout = PlotInstanceLayout {
+            data_bounds: Bounds {
+                x: interval(0., 1.),
+                y: interval(-0.5, 2.5),
+            },
+            y_scale: FIVE,
+            secondary_y: Some(interval(0., 300.)),
+            ..categorical_layout(interval(0., 1.))
+        };
+
+        // The primary axis can only zoom out as far as its categories.
+        layout.zoom((150., 150.), 2.);
+        assert_eq!(layout.data_bounds.y, interval(-0.5, 4.5));
+        assert_eq!(layout.secondary_y, Some(interval(0., 500.)));
+    }
+
+    #[test]
+    fn panning_stops_at_the_last_category() {
+        let mut layout = categorical_layout(interval(1.5, 4.5));
+        for _ in 0..3 {
+            layout.drag((150., 150.), (150., 150.), (50., 150.));
+        }
+        layout.end_drag();
+        assert_eq!(layout.data_bounds.x, interval(1.5, 4.5));
+    }
+}
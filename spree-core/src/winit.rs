@@ -17,6 +17,10 @@ use winit::{
 
 use crate::{PlotInstanceLayout, PlotLayout, State, gpu::Renderer};
 
+/// How far in physical pixels the mouse can move between being pressed and
+/// released for it to count as a click.
+const CLICK_DISTANCE: f64 = 4.;
+
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum App<'s, S>
 where
@@ -180,6 +184,21 @@ where
                     }
                     WindowEvent::MouseInput { state, button, .. } => {
                         if button == MouseButton::Left {
+                            // A press and release without dragging is a click.
+                            if state == ElementState::Released
+                                && let Some(start) = input.is_mouse_down
+                                && let Some(position) = input.prior_position
+                                && start.distance(position) < CLICK_DISTANCE
+                                && renderer.toggle_legend_entry(position)
+                            {
+                                window.request_redraw();
+                            }
+
+                            if state == ElementState::Released && input.is_mouse_down.is_some() {
+                                layout.end_drag();
+                                window.request_redraw();
+                            }
+
                             input.is_mouse_down = if state == ElementState::Pressed {
                                 input.prior_position
                             } else {
@@ -189,6 +208,9 @@ where
                     }
                     WindowEvent::CursorLeft { .. } => {
                         input.prior_position = None;
+                        if renderer.hover(None) {
+                            window.request_redraw();
+                        }
                     }
                     WindowEvent::MouseWheel { delta, .. } => {
                         let factor = match delta {
@@ -225,9 +247,14 @@ where
                         }
 
                         input.prior_position = Some(position);
+                        if renderer.hover(Some(position)) {
+                            window.request_redraw();
+                        }
                     }
                     WindowEvent::RedrawRequested => {
-                        renderer.render(state.layers(layout).into_iter(), layout);
+                        if renderer.render(state.layers(layout).into_iter(), layout) {
+                            window.request_redraw();
+                        }
                     }
                     _ => {}
                 }